@@ -1,16 +1,198 @@
 #![doc = include_str!("../README.md")]
 
-use datafusion::arrow::array::{Array, ArrayRef, StringArray};
-use datafusion::arrow::datatypes::DataType;
+use datafusion::arrow::array::{
+    downcast_dictionary_array, downcast_run_array, new_null_array, Array, ArrayRef, AsArray,
+    BinaryArray, BinaryBuilder, BooleanArray, BooleanBuilder, DictionaryArray,
+    FixedSizeBinaryArray, GenericListArray, GenericListBuilder, GenericStringArray,
+    GenericStringBuilder, Int32Array, Int64Array, LargeListArray, LargeStringArray, ListArray,
+    ListBuilder, OffsetSizeTrait, PrimitiveArray, RunArray, StringArray, StringBuilder,
+    StringViewArray, StringViewBuilder, StructArray, UInt32Array,
+};
+use datafusion::arrow::buffer::OffsetBuffer;
+#[cfg(feature = "rayon")]
+use datafusion::arrow::compute::concat;
+use datafusion::arrow::compute::{cast, max};
+use datafusion::arrow::datatypes::{
+    ArrowDictionaryKeyType, DataType, Field, Fields, Int64Type, RunEndIndexType,
+};
+use datafusion::common::ExprSchema;
 use datafusion::error::{DataFusionError, Result};
-use datafusion::logical_expr::{create_udf, ScalarFunctionImplementation, ScalarUDF, Volatility};
+use datafusion::execution::FunctionRegistry;
+use datafusion::logical_expr::{
+    create_udf, Expr, ScalarFunctionImplementation, ScalarUDF, ScalarUDFImpl, Signature,
+    SimpleScalarUDF, TypeSignature, Volatility,
+};
 use datafusion::physical_plan::ColumnarValue;
+use datafusion::prelude::SessionContext;
 use datafusion::scalar::ScalarValue;
-use regex::Regex;
-use std::sync::Arc;
+use regex::{Captures, Match, Regex, RegexBuilder};
+use std::any::Any;
+use std::borrow::Cow;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, LazyLock, Mutex, MutexGuard, PoisonError, RwLock};
+
+/// Generates a `create_udf` based UDF taking a `StringArray` input, a constant pattern and
+/// further constant arguments, so that every UDF unpacks its arguments the same way.
+///
+/// Each extra argument is declared as `name: Type`, with `Type` either `Utf8` (bound as
+/// `&str`) or `UInt32` (bound as `usize`). The body evaluates to `Result<ArrayRef>`.
+macro_rules! regex_udf {
+    (
+        $name:literal -> $return_type:expr,
+        |$input:ident, $pattern:ident $(, $arg:ident: $arg_type:ident)*| $body:expr
+    ) => {{
+        let input_types = vec![
+            DataType::Utf8, // StringArray (input)
+            DataType::Utf8, // String (pattern)
+            $(regex_udf!(@type $arg_type),)*
+        ];
+
+        let arity = input_types.len();
+        let fun: ScalarFunctionImplementation =
+            Arc::new(move |args: &[ColumnarValue]| -> Result<ColumnarValue> {
+                check_arity($name, args, arity)?;
+                let $input = string_array_arg($name, &args[0])?;
+                let $pattern = string_arg($name, args.get(1), "pattern")?;
+                let mut _args = args.iter().skip(2);
+                $(let $arg = regex_udf!(@arg $arg_type, $name, $arg, _args.next());)*
+                Ok(ColumnarValue::Array($body?))
+            });
+
+        // Mark as immutable (does not depend on the data)
+        create_udf($name, input_types, $return_type, Volatility::Immutable, fun)
+    }};
+    (@type Utf8) => {
+        DataType::Utf8
+    };
+    (@type UInt32) => {
+        DataType::UInt32
+    };
+    (@arg Utf8, $name:literal, $arg:ident, $value:expr) => {
+        string_arg($name, $value, &stringify!($arg).replace('_', " "))?
+    };
+    (@arg UInt32, $name:literal, $arg:ident, $value:expr) => {
+        uint32_arg($name, $value, &stringify!($arg).replace('_', " "))?
+    };
+}
+
+/// Returns an error naming the expected number of arguments unless `args` has `expected`
+/// arguments, instead of panicking on a missing one.
+fn check_arity(udf_name: &str, args: &[ColumnarValue], expected: usize) -> Result<()> {
+    check_arity_range(udf_name, args, expected, expected)
+}
+
+/// Same as [`check_arity`], for UDFs with optional trailing arguments.
+fn check_arity_range(udf_name: &str, args: &[ColumnarValue], min: usize, max: usize) -> Result<()> {
+    if (min..=max).contains(&args.len()) {
+        return Ok(());
+    }
+    Err(RegexpError::ArityMismatch {
+        function: udf_name.to_string(),
+        min,
+        max,
+        actual: args.len(),
+    }
+    .into())
+}
+
+/// Unpacks the `StringArray` input of a UDF, after [`check_arity`].
+fn string_array_arg<'a>(udf_name: &str, arg: &'a ColumnarValue) -> Result<&'a StringArray> {
+    match arg {
+        ColumnarValue::Array(arr) => arr
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .ok_or_else(|| unsupported_input(udf_name, "a Utf8 column", arr.data_type())),
+        _ => Err(unsupported_input(
+            udf_name,
+            "a Utf8 column",
+            &arg.data_type(),
+        )),
+    }
+}
+
+/// Unpacks a constant string argument of a [`regex_udf!`] UDF, e.g. the pattern.
+fn string_arg<'a>(udf_name: &str, arg: Option<&'a ColumnarValue>, name: &str) -> Result<&'a str> {
+    match arg {
+        Some(ColumnarValue::Scalar(ScalarValue::Utf8(Some(s)))) => Ok(s),
+        _ => Err(constant_arg_error(
+            udf_name,
+            arg,
+            &format!("a non-null Utf8 constant as {name}"),
+        )),
+    }
+}
+
+/// Unpacks a constant `UInt32` argument of a [`regex_udf!`] UDF.
+fn uint32_arg(udf_name: &str, arg: Option<&ColumnarValue>, name: &str) -> Result<usize> {
+    match arg {
+        Some(ColumnarValue::Scalar(ScalarValue::UInt32(Some(i)))) => Ok(*i as usize),
+        _ => Err(constant_arg_error(
+            udf_name,
+            arg,
+            &format!("a non-null UInt32 constant as {name}"),
+        )),
+    }
+}
+
+/// Unpacks a constant `Int64` argument that has to fit in `i32`, such as a position,
+/// `None` when it is null.
+fn int64_arg(udf_name: &str, arg: Option<&ColumnarValue>, name: &str) -> Result<Option<i32>> {
+    match arg {
+        Some(ColumnarValue::Scalar(ScalarValue::Int64(i))) => i
+            .map(|i| {
+                i32::try_from(i)
+                    .map_err(|_| out_of_range(udf_name, name, i, DataType::Int64, "Int32"))
+            })
+            .transpose(),
+        _ => Err(constant_arg_error(
+            udf_name,
+            arg,
+            &format!("an Int64 constant as {name}"),
+        )),
+    }
+}
+
+/// Error for a missing or mistyped constant argument, `expected` describing the argument.
+fn constant_arg_error(
+    udf_name: &str,
+    arg: Option<&ColumnarValue>,
+    expected: &str,
+) -> DataFusionError {
+    let actual = arg.map_or(DataType::Null, ColumnarValue::data_type);
+    unsupported_input(udf_name, expected, &actual)
+}
+
+/// Error for an integer argument `name` of type `actual` whose `value` does not fit in
+/// the `target` type it is converted to.
+fn out_of_range(
+    udf_name: &str,
+    name: &str,
+    value: impl Display,
+    actual: DataType,
+    target: &str,
+) -> DataFusionError {
+    RegexpError::UnsupportedInputType {
+        function: udf_name.to_string(),
+        expected: format!("{name} within the {target} range"),
+        actual,
+        hint: Some(format!("{value} is out of range for {target}")),
+    }
+    .into()
+}
 
 /// Extracts a capture group from strings using a regular expression pattern.
 ///
+/// The output has exactly the same null positions as the input, whatever the pattern:
+/// null rows stay null and every other row yields a string, an empty one when the
+/// pattern or group does not match.
+///
+/// A pattern without capture groups, such as `\d+`, still has group 0: `group_index = 0`
+/// returns the full match, while any higher index yields an empty string like a row the
+/// pattern does not match.
+///
 /// # Arguments
 /// * `input` - Input string array to process
 /// * `pattern` - Regular expression pattern to match
@@ -36,199 +218,7578 @@ use std::sync::Arc;
 /// assert!(result_array.is_null(2));
 /// ```
 pub fn regexp_extract(input: &StringArray, pattern: &str, group_index: usize) -> Result<ArrayRef> {
-    let re = Regex::new(pattern)
-        .map_err(|e| datafusion::error::DataFusionError::Execution(e.to_string()))?;
-
-    let array: StringArray = input
-        .iter()
-        .map(|optional_data| {
-            optional_data.map(|data| {
-                re.captures(data)
-                    .and_then(|captures| captures.get(group_index))
-                    .map(|m| m.as_str().to_string())
-                    .unwrap_or_default()
-            })
-        })
-        .collect();
-
-    Ok(Arc::new(array))
+    Ok(Arc::new(regexp_extract_generic(
+        input,
+        pattern,
+        group_index,
+    )?))
 }
 
-/// Creates a DataFusion UDF that extracts a capture group from strings using a regular expression pattern.
+/// Extracts a capture group from strings of any offset size using a regular expression pattern.
+///
+/// The implementation behind [`regexp_extract`] and [`regexp_extract_large`], returning
+/// the same array type as the input.
+///
+/// # Arguments
+/// * `input` - Input `StringArray` or `LargeStringArray` to process
+/// * `pattern` - Regular expression pattern to match
+/// * `group_index` - Index of the capture group to extract (0 for full match)
 ///
 /// # Returns
-/// * `ScalarUDF` - User Defined Function that accepts:
-///   - input: string array to process
-///   - pattern: regex pattern string
-///   - group_index: capture group index (as UInt32).
-pub fn create_regexp_extract() -> ScalarUDF {
-    // Create the UDF signature
-    let input_types = vec![
-        DataType::Utf8,   // First input type: StringArray (Utf8)
-        DataType::Utf8,   // Second input type: String (Pattern)
-        DataType::UInt32, // Third input type: UInt32 (group_index)
-    ];
+/// * `Result<GenericStringArray<O>>` - Array containing extracted strings or nulls
+///
+/// # Example
+/// ```
+/// use flarion_task::regexp_extract_generic;
+/// use datafusion::arrow::array::{Array, LargeStringArray};
+///
+/// let input = LargeStringArray::from(vec![Some("hello123"), None]);
+/// let result = regexp_extract_generic(&input, r"([a-z]+)(\d+)", 1).unwrap();
+///
+/// assert_eq!(result.value(0), "hello");
+/// assert!(result.is_null(1));
+/// ```
+pub fn regexp_extract_generic<O: OffsetSizeTrait>(
+    input: &GenericStringArray<O>,
+    pattern: &str,
+    group_index: usize,
+) -> Result<GenericStringArray<O>> {
+    extract_with_config(input, pattern, &RegexpExtractConfig::new(group_index))
+}
 
-    let return_type = DataType::Utf8; // The return type will be StringArray (Utf8)
+/// Compiles `pattern` and extracts from `input` as described by `config`.
+fn extract_with_config<O: OffsetSizeTrait>(
+    input: &GenericStringArray<O>,
+    pattern: &str,
+    config: &RegexpExtractConfig,
+) -> Result<GenericStringArray<O>> {
+    // Nothing to extract from, so the pattern does not even need to be compiled
+    if input.is_empty() {
+        return Ok(GenericStringArray::new_null(0));
+    }
 
-    let volatility = Volatility::Immutable; // Mark as immutable (does not depend on the data)
+    let re = compile_extract_regex("regexp_extract", &config.pattern(pattern), "")?;
+    extract_string_array(input, &re, config)
+}
 
-    // Define the implementation of the function
-    let fun: ScalarFunctionImplementation =
-        Arc::new(|args: &[ColumnarValue]| -> Result<ColumnarValue> {
-            let input = match &args[0] {
-                ColumnarValue::Array(arr) => {
-                    arr.as_any().downcast_ref::<StringArray>().ok_or_else(|| {
-                        DataFusionError::Execution("Expected StringArray".to_string())
-                    })?
-                }
-                _ => {
-                    return Err(DataFusionError::Execution(
-                        "Expected StringArray".to_string(),
-                    ))
-                }
-            };
+/// What to produce for a row where the pattern does not match.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum NoMatchBehavior {
+    /// An empty string (same as Spark).
+    #[default]
+    EmptyString,
+    /// A null value.
+    Null,
+    /// Fail the whole call with an error.
+    Error,
+    /// The given value.
+    Default(String),
+}
 
-            let pattern = match &args[1] {
-                ColumnarValue::Scalar(ScalarValue::Utf8(Some(s))) => s,
-                _ => {
-                    return Err(DataFusionError::Execution(
-                        "Expected pattern string".to_string(),
-                    ))
-                }
-            };
+/// Options for [`regexp_extract_with_config`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RegexpExtractConfig {
+    /// Index of the capture group to extract (0 for full match)
+    pub group_index: usize,
+    /// Output for rows where the pattern does not match
+    pub no_match: NoMatchBehavior,
+    /// Also use `no_match` for rows where the pattern matches but the group did not
+    /// participate in the match, instead of an empty string
+    pub missing_group_as_no_match: bool,
+    /// Trim leading and trailing ASCII whitespace from the extracted group
+    pub trim: bool,
+    /// Case folding applied to the extracted group, after trimming
+    pub case: CaseTransform,
+    /// Match the pattern as a literal string, with every regex special character escaped,
+    /// for patterns coming from user input. The only group is then the full match
+    pub literal: bool,
+    /// Maximum length in characters of the extracted group, longer groups are truncated
+    /// at a character boundary after trimming and case folding. No limit by default
+    pub max_len: Option<usize>,
+}
 
-            let group_index = match &args[2] {
-                ColumnarValue::Scalar(ScalarValue::UInt32(Some(i))) => *i as usize,
-                _ => return Err(DataFusionError::Execution("Expected UInt32".to_string())),
-            };
+/// Case transformation of the groups extracted by [`regexp_extract_with_config`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CaseTransform {
+    /// The group as matched.
+    #[default]
+    None,
+    /// The group lowercased, following Unicode rules.
+    Lower,
+    /// The group uppercased, following Unicode rules.
+    Upper,
+}
 
-            Ok(ColumnarValue::Array(regexp_extract(
-                input,
-                pattern,
-                group_index,
-            )?))
-        });
+impl RegexpExtractConfig {
+    /// Extracts `group_index` with the Spark-compatible defaults for everything else.
+    pub fn new(group_index: usize) -> Self {
+        Self {
+            group_index,
+            ..Default::default()
+        }
+    }
 
-    // Create the UDF and return it
-    create_udf("regexp_extract", input_types, return_type, volatility, fun)
+    /// The regex `pattern` stands for, escaped if it is `literal`.
+    fn pattern<'a>(&self, pattern: &'a str) -> Cow<'a, str> {
+        if self.literal {
+            Cow::Owned(regex::escape(pattern))
+        } else {
+            Cow::Borrowed(pattern)
+        }
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::regexp_extract;
-    use datafusion::arrow::array::{Array, StringArray};
-
-    #[test]
-    fn test_regexp_extract_basic() {
-        let input = StringArray::from(vec![Some("hello123"), Some("world456"), None]);
-        let pattern = r"([a-z]+)(\d+)";
+/// Extracts a capture group from strings with configurable behavior.
+///
+/// Null inputs always produce nulls, `config.no_match` only decides the output for rows
+/// where the pattern does not match at all. A match whose group did not participate
+/// yields an empty string, unless `config.missing_group_as_no_match` is set. An empty input returns an empty array without
+/// compiling the pattern. With `config.trim` surrounding ASCII whitespace is removed from
+/// the extracted groups, not from the `no_match` values, and `config.case` folds their case.
+/// `config.max_len` truncates the extracted groups to that many characters.
+/// With `config.literal` the pattern is matched as a plain string.
+///
+/// # Arguments
+/// * `input` - Input string array to process
+/// * `pattern` - Regular expression pattern to match
+/// * `config` - Group to extract and how to handle rows without a match
+///
+/// # Returns
+/// * `Result<ArrayRef>` - Arrow array containing extracted strings or nulls.
+///   Returns an error for a non-matching row with [`NoMatchBehavior::Error`].
+///
+/// # Example
+/// ```
+/// use flarion_task::{regexp_extract_with_config, NoMatchBehavior, RegexpExtractConfig};
+/// use datafusion::arrow::array::{Array, StringArray};
+///
+/// let input = StringArray::from(vec![Some("hello123"), Some("hello")]);
+/// let config = RegexpExtractConfig {
+///     group_index: 2,
+///     no_match: NoMatchBehavior::Default("n/a".to_string()),
+///     ..Default::default()
+/// };
+/// let result = regexp_extract_with_config(&input, r"([a-z]+)(\d+)", &config).unwrap();
+/// let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+///
+/// assert_eq!(result_array.value(0), "123");
+/// assert_eq!(result_array.value(1), "n/a");
+/// ```
+pub fn regexp_extract_with_config(
+    input: &StringArray,
+    pattern: &str,
+    config: &RegexpExtractConfig,
+) -> Result<ArrayRef> {
+    Ok(Arc::new(extract_with_config(input, pattern, config)?))
+}
 
-        // Test group 0 (full match)
-        let result = regexp_extract(&input, pattern, 0).unwrap();
-        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
-        assert_eq!(result_array.value(0), "hello123");
-        assert_eq!(result_array.value(1), "world456");
-        assert!(result_array.is_null(2));
+/// Extracts a capture group from a single string using a regular expression pattern.
+///
+/// Same as [`regexp_extract_opt`] with `null_on_no_match` set, for a single value instead
+/// of an array. The pattern is compiled through the same process-wide cache.
+///
+/// # Arguments
+/// * `input` - String to process
+/// * `pattern` - Regular expression pattern to match
+/// * `group_index` - Index of the capture group to extract (0 for full match)
+///
+/// # Returns
+/// * `Result<Option<String>>` - The extracted group, `None` if the pattern does not match.
+///   A group that did not participate in the match, or that the pattern does not have,
+///   yields `Some("")`.
+///
+/// # Example
+/// ```
+/// use flarion_task::regexp_extract_str;
+///
+/// let result = regexp_extract_str("hello123", r"([a-z]+)(\d+)", 2).unwrap();
+/// assert_eq!(result.as_deref(), Some("123"));
+/// assert_eq!(regexp_extract_str("hello", r"([a-z]+)(\d+)", 2).unwrap(), None);
+/// ```
+pub fn regexp_extract_str(
+    input: &str,
+    pattern: &str,
+    group_index: usize,
+) -> Result<Option<String>> {
+    let config = RegexpExtractConfig {
+        group_index,
+        no_match: NoMatchBehavior::Null,
+        ..Default::default()
+    };
+    regexp_extract_str_with_config(input, pattern, &config)
+}
 
-        // Test group 1 (letters)
-        let result = regexp_extract(&input, pattern, 1).unwrap();
-        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
-        assert_eq!(result_array.value(0), "hello");
-        assert_eq!(result_array.value(1), "world");
-        assert!(result_array.is_null(2));
+/// Extracts a capture group from a single string with configurable behavior.
+///
+/// Same as [`regexp_extract_with_config`] for a single value, e.g. the default config
+/// returns `Some("")` like Spark when the pattern does not match.
+///
+/// # Arguments
+/// * `input` - String to process
+/// * `pattern` - Regular expression pattern to match
+/// * `config` - Group to extract and how to handle a string without a match
+///
+/// # Returns
+/// * `Result<Option<String>>` - The extracted group, or the value chosen by
+///   `config.no_match` (`None` for [`NoMatchBehavior::Null`])
+///
+/// # Example
+/// ```
+/// use flarion_task::{regexp_extract_str_with_config, RegexpExtractConfig};
+///
+/// let config = RegexpExtractConfig::new(1);
+/// let result = regexp_extract_str_with_config("hello", r"(\d+)", &config).unwrap();
+/// assert_eq!(result.as_deref(), Some(""));
+/// ```
+pub fn regexp_extract_str_with_config(
+    input: &str,
+    pattern: &str,
+    config: &RegexpExtractConfig,
+) -> Result<Option<String>> {
+    let re = compile_extract_regex("regexp_extract", &config.pattern(pattern), "")?;
+    let group_in_range = group_in_range(&re, config.group_index);
+    Ok(extract_value(&re, input, config, group_in_range)?.map(Cow::into_owned))
+}
 
-        // Test group 2 (numbers)
-        let result = regexp_extract(&input, pattern, 2).unwrap();
-        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
-        assert_eq!(result_array.value(0), "123");
-        assert_eq!(result_array.value(1), "456");
-        assert!(result_array.is_null(2));
-    }
+/// Extracts a capture group from large strings using a regular expression pattern.
+///
+/// Same as [`regexp_extract`] but for `LargeStringArray` (`LargeUtf8`) input, which is
+/// used when a column holds more than 2GB of string data.
+///
+/// # Returns
+/// * `Result<ArrayRef>` - Arrow `LargeStringArray` containing extracted strings or nulls
+///
+/// # Example
+/// ```
+/// use flarion_task::regexp_extract_large;
+/// use datafusion::arrow::array::{Array, LargeStringArray};
+///
+/// let input = LargeStringArray::from(vec![Some("hello123"), None]);
+/// let result = regexp_extract_large(&input, r"([a-z]+)(\d+)", 2).unwrap();
+/// let result_array = result.as_any().downcast_ref::<LargeStringArray>().unwrap();
+///
+/// assert_eq!(result_array.value(0), "123");
+/// assert!(result_array.is_null(1));
+/// ```
+pub fn regexp_extract_large(
+    input: &LargeStringArray,
+    pattern: &str,
+    group_index: usize,
+) -> Result<ArrayRef> {
+    Ok(Arc::new(regexp_extract_generic(
+        input,
+        pattern,
+        group_index,
+    )?))
+}
 
-    #[test]
-    fn test_regexp_extract_no_match() {
-        let input = StringArray::from(vec![Some("abc"), Some("def")]);
-        let pattern = r"\d+";
-        let result = regexp_extract(&input, pattern, 0).unwrap();
-        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
-        // No match should return empty string (Same as Spark behavior).
-        assert_eq!(result_array.value(0), "");
-        assert_eq!(result_array.value(1), "");
-    }
+/// Extracts a capture group from string views using a regular expression pattern.
+///
+/// Same as [`regexp_extract`] but for `StringViewArray` (`Utf8View`) input.
+///
+/// # Returns
+/// * `Result<ArrayRef>` - Arrow `StringArray` containing extracted strings or nulls
+///
+/// # Example
+/// ```
+/// use flarion_task::regexp_extract_view;
+/// use datafusion::arrow::array::{Array, StringArray, StringViewArray};
+///
+/// let input = StringViewArray::from(vec![Some("hello123"), None]);
+/// let result = regexp_extract_view(&input, r"([a-z]+)(\d+)", 1).unwrap();
+/// let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+///
+/// assert_eq!(result_array.value(0), "hello");
+/// assert!(result_array.is_null(1));
+/// ```
+pub fn regexp_extract_view(
+    input: &StringViewArray,
+    pattern: &str,
+    group_index: usize,
+) -> Result<ArrayRef> {
+    let re = compile_extract_regex("regexp_extract", pattern, "")?;
 
-    #[test]
-    fn test_regexp_extract_invalid_group() {
-        let input = StringArray::from(vec![Some("test123")]);
-        let pattern = r"(\w+)";
-        let result = regexp_extract(&input, pattern, 99).unwrap();
-        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
-        assert_eq!(result_array.value(0), ""); // Invalid group index returns empty string (Same as Spark behavior).
-    }
+    let array: StringArray =
+        extract_with_regex(input, &re, &RegexpExtractConfig::new(group_index))?;
 
-    #[test]
-    fn test_regexp_extract_optional_groups() {
-        let input = StringArray::from(vec![Some("abc123"), Some("def"), Some("456")]);
-        let pattern = r"([a-z]+)?(\d+)?";
+    Ok(Arc::new(array))
+}
 
-        // Test group 1 (letters)
-        let result = regexp_extract(&input, pattern, 1).unwrap();
-        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
-        assert_eq!(result_array.value(0), "abc");
-        assert_eq!(result_array.value(1), "def");
-        assert_eq!(result_array.value(2), "");
+/// Extracts a capture group from a dictionary-encoded string array.
+///
+/// The regex is applied once per distinct dictionary value rather than once per row, and
+/// the result keeps the input's keys, so the output is a `DictionaryArray` with the same
+/// key type whose values are the extracted strings.
+///
+/// # Returns
+/// * `Result<ArrayRef>` - Arrow `DictionaryArray` containing extracted strings or nulls
+///
+/// # Example
+/// ```
+/// use flarion_task::regexp_extract_dictionary;
+/// use datafusion::arrow::array::{Array, ArrayAccessor, DictionaryArray, StringArray};
+/// use datafusion::arrow::datatypes::Int32Type;
+///
+/// let input: DictionaryArray<Int32Type> = vec![Some("a1"), Some("b2"), Some("a1"), None]
+///     .into_iter()
+///     .collect();
+/// let result = regexp_extract_dictionary(&input, r"([a-z])(\d)", 2).unwrap();
+/// let result_array = result.as_any().downcast_ref::<DictionaryArray<Int32Type>>().unwrap();
+/// let values = result_array.downcast_dict::<StringArray>().unwrap();
+///
+/// assert_eq!(values.value(0), "1");
+/// assert_eq!(values.value(1), "2");
+/// assert_eq!(values.value(2), "1");
+/// assert!(result_array.is_null(3));
+/// ```
+pub fn regexp_extract_dictionary<K: ArrowDictionaryKeyType>(
+    input: &DictionaryArray<K>,
+    pattern: &str,
+    group_index: usize,
+) -> Result<ArrayRef> {
+    let re = compile_extract_regex("regexp_extract", pattern, "")?;
 
-        // Test group 2 (numbers)
-        let result = regexp_extract(&input, pattern, 2).unwrap();
-        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
-        assert_eq!(result_array.value(0), "123");
-        assert_eq!(result_array.value(1), "");
-        assert_eq!(result_array.value(2), "456");
-    }
+    Ok(Arc::new(input.with_values(extract_array(
+        input.values(),
+        &re,
+        &RegexpExtractConfig::new(group_index),
+    )?)))
+}
 
-    #[test]
-    fn test_regexp_extract_special_chars() {
-        let input = StringArray::from(vec![Some("test.123"), Some("test-456"), Some("test_789")]);
-        let pattern = r"test(.)(\d+)";
+/// Extracts a capture group, matching only from a character offset onwards, like the
+/// `position` parameter of Oracle's regex functions.
+///
+/// The pattern is matched against the substring starting at `start`, so `^` anchors at
+/// that offset and a match lying before it is ignored.
+///
+/// # Arguments
+/// * `input` - Input string array to process
+/// * `pattern` - Regular expression pattern to match
+/// * `group_index` - Index of the capture group to extract (0 for full match)
+/// * `start` - 0-based character offset the search starts at
+///
+/// # Returns
+/// * `Result<ArrayRef>` - Arrow `StringArray` containing extracted strings or nulls. Rows
+///   without a match from `start` on, including rows shorter than `start`, yield empty
+///   strings like [`regexp_extract`].
+///
+/// # Example
+/// ```
+/// use flarion_task::regexp_extract_from;
+/// use datafusion::arrow::array::{AsArray, StringArray};
+///
+/// let input = StringArray::from(vec!["id=1 id=22"]);
+/// let result = regexp_extract_from(&input, r"id=(\d+)", 1, 4).unwrap();
+/// assert_eq!(result.as_string::<i32>().value(0), "22");
+/// ```
+pub fn regexp_extract_from(
+    input: &StringArray,
+    pattern: &str,
+    group_index: usize,
+    start: usize,
+) -> Result<ArrayRef> {
+    let re = compile_extract_regex("regexp_extract_from", pattern, "")?;
+    let config = RegexpExtractConfig::new(group_index);
+    let group_in_range = group_in_range(&re, group_index);
 
-        // Test group 1 (special char)
-        let result = regexp_extract(&input, pattern, 1).unwrap();
-        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
-        assert_eq!(result_array.value(0), ".");
-        assert_eq!(result_array.value(1), "-");
-        assert_eq!(result_array.value(2), "_");
+    let mut builder = StringBuilder::with_capacity(input.len(), string_data_len(input));
+    for optional_data in input.iter() {
+        let Some(data) = optional_data else {
+            builder.append_null();
+            continue;
+        };
+        // Byte offset of the character at `start`, rows shorter than it have nothing to match
+        let data = match data.char_indices().nth(start) {
+            Some((offset, _)) => &data[offset..],
+            None if data.chars().count() == start => "",
+            None => {
+                builder.append_value("");
+                continue;
+            }
+        };
+        builder.append_option(extract_value(&re, data, &config, group_in_range)?);
     }
 
-    #[test]
-    fn test_regexp_extract_empty_input() {
-        let input = StringArray::from(vec![Some("")]);
-        let pattern = r".*";
-        let result = regexp_extract(&input, pattern, 0).unwrap();
-        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
-        assert_eq!(result_array.value(0), "");
-    }
+    Ok(Arc::new(builder.finish()))
+}
 
-    #[test]
-    fn test_regexp_extract_number_range() {
-        let input = StringArray::from(vec![Some("100-200")]);
-        let result = regexp_extract(&input, r"(\d+)-(\d+)", 1).unwrap();
+/// Extracts a capture group from an array of any supported type, dispatching on its
+/// [`DataType`] like an Arrow compute kernel.
+///
+/// The `regexp_extract` UDF dispatches the same way for a constant pattern. `Utf8`,
+/// `Utf8View` and numeric input yield `Utf8`, `LargeUtf8` input yields `LargeUtf8`,
+/// dictionary, run-end encoded and list input keep their encoding with extracted values.
+///
+/// # Arguments
+/// * `input` - Array to process
+/// * `pattern` - Regular expression pattern to match
+/// * `group_index` - Index of the capture group to extract (0 for full match)
+///
+/// # Returns
+/// * `Result<ArrayRef>` - Arrow array containing extracted strings or nulls. Returns an
+///   error listing the supported types for any other input.
+///
+/// # Example
+/// ```
+/// use flarion_task::regexp_extract_dyn;
+/// use datafusion::arrow::array::{Array, ArrayRef, AsArray, LargeStringArray};
+/// use std::sync::Arc;
+///
+/// let input: ArrayRef = Arc::new(LargeStringArray::from(vec![Some("hello123"), None]));
+/// let result = regexp_extract_dyn(input.as_ref(), r"([a-z]+)(\d+)", 2).unwrap();
+/// let result_array = result.as_string::<i64>();
+///
+/// assert_eq!(result_array.value(0), "123");
+/// assert!(result_array.is_null(1));
+/// ```
+pub fn regexp_extract_dyn(
+    input: &dyn Array,
+    pattern: &str,
+    group_index: usize,
+) -> Result<ArrayRef> {
+    let re = compile_extract_regex("regexp_extract", pattern, "")?;
+
+    extract_array(input, &re, &RegexpExtractConfig::new(group_index))
+}
+
+/// Extracts a capture group from the string representation of numbers.
+///
+/// Saves an explicit cast to a string column, numbers are formatted like
+/// `CAST(x AS VARCHAR)` does, e.g. `123.45` for a float and `-7` for an integer.
+///
+/// # Arguments
+/// * `input` - Array of any integer, float or decimal type
+/// * `pattern` - Regular expression pattern to match
+/// * `group_index` - Index of the capture group to extract (0 for full match)
+///
+/// # Returns
+/// * `Result<ArrayRef>` - Arrow `StringArray` containing extracted strings or nulls.
+///   Returns an error for input that is not numeric.
+///
+/// # Example
+/// ```
+/// use flarion_task::regexp_extract_numeric;
+/// use datafusion::arrow::array::{Array, AsArray, Float64Array};
+///
+/// let input = Float64Array::from(vec![Some(123.45), None]);
+/// let result = regexp_extract_numeric(&input, r"(\d+)\.(\d+)", 2).unwrap();
+/// let result_array = result.as_string::<i32>();
+///
+/// assert_eq!(result_array.value(0), "45");
+/// assert!(result_array.is_null(1));
+/// ```
+pub fn regexp_extract_numeric(
+    input: &dyn Array,
+    pattern: &str,
+    group_index: usize,
+) -> Result<ArrayRef> {
+    if !input.data_type().is_numeric() {
+        return Err(unsupported_input(
+            "regexp_extract_numeric",
+            "a numeric array",
+            input.data_type(),
+        ));
+    }
+    let re = compile_extract_regex("regexp_extract_numeric", pattern, "")?;
+
+    extract_array(input, &re, &RegexpExtractConfig::new(group_index))
+}
+
+/// Applies an already compiled regex to any supported string array type, as described
+/// by `config`. `LargeUtf8` input yields `LargeUtf8` output, dictionary input yields
+/// a dictionary with the same keys, list input yields a list with the same offsets and
+/// nulls, everything else yields `Utf8`.
+fn extract_array(
+    input: &dyn Array,
+    re: &ExtractRegex,
+    config: &RegexpExtractConfig,
+) -> Result<ArrayRef> {
+    let result: ArrayRef = match input.data_type() {
+        DataType::Utf8 => {
+            let input = input.as_any().downcast_ref::<StringArray>().unwrap();
+            let array: StringArray =
+                extract_string_array(input, re, config)?;
+            Arc::new(array)
+        }
+        DataType::LargeUtf8 => {
+            let input = input.as_any().downcast_ref::<LargeStringArray>().unwrap();
+            let array: LargeStringArray =
+                extract_string_array(input, re, config)?;
+            Arc::new(array)
+        }
+        DataType::Utf8View => {
+            let input = input.as_any().downcast_ref::<StringViewArray>().unwrap();
+            let array: StringArray =
+                extract_with_regex(input, re, config)?;
+            Arc::new(array)
+        }
+        DataType::Dictionary(_, _) => downcast_dictionary_array!(
+            input => Arc::new(input.with_values(extract_array(input.values(), re, config)?)),
+            other => {
+                return Err(unsupported_input(
+                    "regexp_extract",
+                    "a dictionary with integer keys",
+                    other,
+                ))
+            }
+        ),
+        DataType::RunEndEncoded(_, _) => downcast_run_array!(
+            input => extract_run_array(input, re, config)?,
+            other => {
+                return Err(unsupported_input(
+                    "regexp_extract",
+                    "a run-end encoded array with Int16, Int32 or Int64 run ends",
+                    other,
+                ))
+            }
+        ),
+        DataType::List(field) => {
+            let input = input.as_list::<i32>();
+            let values = extract_array(input.values().as_ref(), re, config)?;
+            let field = field.as_ref().clone().with_data_type(values.data_type().clone());
+            Arc::new(ListArray::try_new(
+                Arc::new(field),
+                input.offsets().clone(),
+                values,
+                input.nulls().cloned(),
+            )?)
+        }
+        // Numbers are matched against their string representation
+        data_type if data_type.is_numeric() => {
+            extract_array(cast(input, &DataType::Utf8)?.as_ref(), re, config)?
+        }
+        DataType::Binary | DataType::LargeBinary | DataType::BinaryView => {
+            return Err(binary_input_error(input.data_type()))
+        }
+        other => {
+            return Err(unsupported_input(
+                "regexp_extract",
+                "StringArray, LargeStringArray, StringViewArray, DictionaryArray, RunArray, ListArray or a numeric array",
+                other,
+            ))
+        }
+    };
+
+    Ok(result)
+}
+
+/// [`extract_array`] for run-end encoded input, applied to the run values only.
+///
+/// The output keeps the run ends of the input, so every run is extracted once.
+fn extract_run_array<R: RunEndIndexType>(
+    input: &RunArray<R>,
+    re: &ExtractRegex,
+    config: &RegexpExtractConfig,
+) -> Result<ArrayRef> {
+    let values = extract_array(input.values().as_ref(), re, config)?;
+    // The run ends buffer is not sliced, the logical slice of the input is applied again
+    let run_ends = input.run_ends();
+    let output = RunArray::try_new(
+        &PrimitiveArray::<R>::new(run_ends.inner().clone(), None),
+        values.as_ref(),
+    )?;
+    Ok(Arc::new(output.slice(run_ends.offset(), run_ends.len())))
+}
+
+/// Error for binary input to the string functions, pointing to the binary variant.
+fn binary_input_error(data_type: &DataType) -> DataFusionError {
+    RegexpError::UnsupportedInputType {
+        function: "regexp_extract".to_string(),
+        expected: "strings".to_string(),
+        actual: data_type.clone(),
+        hint: Some(
+            "Use regexp_extract_binary to match the raw bytes, or CAST the column to VARCHAR \
+             if it holds UTF-8 text"
+                .to_string(),
+        ),
+    }
+    .into()
+}
+
+/// Error for an input of a type `udf_name` does not support.
+fn unsupported_input(udf_name: &str, expected: &str, actual: &DataType) -> DataFusionError {
+    RegexpError::UnsupportedInputType {
+        function: udf_name.to_string(),
+        expected: expected.to_string(),
+        actual: actual.clone(),
+        hint: None,
+    }
+    .into()
+}
+
+/// Same as [`extract_array`], but with a per-row pattern array, compiled with `flags`.
+fn extract_array_with_patterns(
+    input: &dyn Array,
+    patterns: &StringArray,
+    flags: &str,
+    config: &RegexpExtractConfig,
+) -> Result<ArrayRef> {
+    let result: ArrayRef = match input.data_type() {
+        DataType::Utf8 => {
+            let input = input.as_any().downcast_ref::<StringArray>().unwrap();
+            let array: StringArray = extract_with_patterns(input, patterns, flags, config)?;
+            Arc::new(array)
+        }
+        DataType::LargeUtf8 => {
+            let input = input.as_any().downcast_ref::<LargeStringArray>().unwrap();
+            let array: LargeStringArray = extract_with_patterns(input, patterns, flags, config)?;
+            Arc::new(array)
+        }
+        DataType::Utf8View => {
+            let input = input.as_any().downcast_ref::<StringViewArray>().unwrap();
+            let array: StringArray = extract_with_patterns(input, patterns, flags, config)?;
+            Arc::new(array)
+        }
+        DataType::Dictionary(_, value_type) => {
+            // Patterns differ per row, not per distinct value, so the dictionary is unpacked
+            // and the result encoded again to keep the declared return type
+            let unpacked = cast(input, value_type)?;
+            let result = extract_array_with_patterns(unpacked.as_ref(), patterns, flags, config)?;
+            cast(result.as_ref(), &extract_return_type(input.data_type()))?
+        }
+        data_type if data_type.is_numeric() => {
+            let input = cast(input, &DataType::Utf8)?;
+            extract_array_with_patterns(input.as_ref(), patterns, flags, config)?
+        }
+        other => return Err(unsupported_input(
+            "regexp_extract",
+            "StringArray, LargeStringArray, StringViewArray, DictionaryArray or a numeric array",
+            other,
+        )),
+    };
+
+    Ok(result)
+}
+
+/// Same as [`extract_array`], but with a per-row group index array overriding
+/// `config.group_index`.
+fn extract_array_with_group_indices(
+    input: &dyn Array,
+    re: &ExtractRegex,
+    group_indices: &Int64Array,
+    config: &RegexpExtractConfig,
+) -> Result<ArrayRef> {
+    let result: ArrayRef = match input.data_type() {
+        DataType::Utf8 => {
+            let input = input.as_any().downcast_ref::<StringArray>().unwrap();
+            let array: StringArray = extract_with_group_indices(input, re, group_indices, config)?;
+            Arc::new(array)
+        }
+        DataType::LargeUtf8 => {
+            let input = input.as_any().downcast_ref::<LargeStringArray>().unwrap();
+            let array: LargeStringArray =
+                extract_with_group_indices(input, re, group_indices, config)?;
+            Arc::new(array)
+        }
+        DataType::Utf8View => {
+            let input = input.as_any().downcast_ref::<StringViewArray>().unwrap();
+            let array: StringArray = extract_with_group_indices(input, re, group_indices, config)?;
+            Arc::new(array)
+        }
+        DataType::Dictionary(_, value_type) => {
+            // Group indices differ per row, not per distinct value, so the dictionary is
+            // unpacked and the result encoded again to keep the declared return type
+            let unpacked = cast(input, value_type)?;
+            let result =
+                extract_array_with_group_indices(unpacked.as_ref(), re, group_indices, config)?;
+            cast(result.as_ref(), &extract_return_type(input.data_type()))?
+        }
+        data_type if data_type.is_numeric() => {
+            let input = cast(input, &DataType::Utf8)?;
+            extract_array_with_group_indices(input.as_ref(), re, group_indices, config)?
+        }
+        other => return Err(unsupported_input(
+            "regexp_extract",
+            "StringArray, LargeStringArray, StringViewArray, DictionaryArray or a numeric array",
+            other,
+        )),
+    };
+
+    Ok(result)
+}
+
+/// Like [`extract_with_regex`], but every row has its own group index.
+///
+/// A null group index yields a null row, a negative one is an error.
+fn extract_with_group_indices<'a, O: OffsetSizeTrait>(
+    input: impl IntoIterator<Item = Option<&'a str>>,
+    re: &ExtractRegex,
+    group_indices: &Int64Array,
+    config: &RegexpExtractConfig,
+) -> Result<GenericStringArray<O>> {
+    let mut builder = GenericStringBuilder::<O>::new();
+    let mut row_config = config.clone();
+    for (optional_data, group_index) in input.into_iter().zip(group_indices.iter()) {
+        let (Some(data), Some(group_index)) = (optional_data, group_index) else {
+            builder.append_null();
+            continue;
+        };
+        let group_index =
+            usize::try_from(group_index).map_err(|_| negative_group_index(group_index))?;
+        row_config.group_index = group_index;
+        builder.append_option(extract_value(
+            re,
+            data,
+            &row_config,
+            group_in_range(re, group_index),
+        )?);
+    }
+    Ok(builder.finish())
+}
+
+/// Remembers the most recently compiled pattern, so that a UDF invoked with the same
+/// constant pattern on every batch compiles it only once.
+///
+/// Together with the [`CapacityHint`] this is the only state of a UDF instance. It sits
+/// behind a mutex that is held only to read or replace the entry, so one instance can be
+/// shared by sessions running queries with different patterns on several threads, each
+/// call getting the regex of its own pattern.
+#[derive(Debug, Default)]
+struct RegexCache {
+    last: Mutex<Option<CachedRegex>>,
+}
+
+/// A pattern and its flags together with its compiled regex.
+type CachedRegex = (String, String, Arc<ExtractRegex>);
+
+impl RegexCache {
+    /// Returns the compiled regex for `pattern` with `flags`, already checked by
+    /// [`check_flags`], compiling it only if either differs from the previous call.
+    fn get(&self, udf_name: &str, pattern: &str, flags: &str) -> Result<Arc<ExtractRegex>> {
+        if let Some((cached, cached_flags, re)) = self.lock().as_ref() {
+            if cached == pattern && cached_flags == flags {
+                return Ok(Arc::clone(re));
+            }
+        }
+
+        // Compiled without holding the lock, a concurrent call with another pattern may
+        // replace the entry in between, which only costs that call a compilation
+        let re = compile_extract_regex(udf_name, pattern, flags)?;
+        *self.lock() = Some((pattern.to_string(), flags.to_string(), Arc::clone(&re)));
+        Ok(re)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Option<CachedRegex>> {
+        // The entry is replaced as a whole, so it is valid after a panic on another thread
+        self.last.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Remembers the largest output size per row of the batches extracted so far, so that a
+/// UDF invoked on many similar batches sizes its output builder from the groups it
+/// actually extracts rather than from the whole input data.
+///
+/// The size is an atomic updated after every batch, so it stays consistent when one
+/// instance runs on several threads. Its only effect is the initial capacity, a builder
+/// that turns out too small grows as usual.
+#[derive(Debug, Default)]
+struct CapacityHint {
+    /// Bytes per row plus one, 0 until a batch has been extracted.
+    bytes_per_row: AtomicUsize,
+}
+
+impl CapacityHint {
+    /// Data capacity of the output builder for `input`, never more than the size of its
+    /// string data, which bounds the extracted substrings.
+    fn data_capacity(&self, input: &StringArray) -> usize {
+        let upper_bound = string_data_len(input);
+        match self.bytes_per_row.load(Ordering::Relaxed) {
+            0 => upper_bound,
+            hint => (hint - 1).saturating_mul(input.len()).min(upper_bound),
+        }
+    }
+
+    /// Takes the size of `output` into account for the next batches.
+    fn record(&self, output: &StringArray) {
+        if output.is_empty() {
+            return;
+        }
+        let bytes_per_row = string_data_len(output).div_ceil(output.len());
+        self.bytes_per_row
+            .fetch_max(bytes_per_row + 1, Ordering::Relaxed);
+    }
+}
+
+/// Default number of compiled patterns kept by the process-wide regex cache.
+pub const DEFAULT_REGEX_CACHE_CAPACITY: usize = 256;
+
+/// Compiled patterns shared by every function and UDF of this crate, keyed by flags and pattern.
+static GLOBAL_REGEX_CACHE: LazyLock<Mutex<LruRegexCache<Regex>>> =
+    LazyLock::new(|| Mutex::new(LruRegexCache::new(DEFAULT_REGEX_CACHE_CAPACITY)));
+
+/// Same as [`GLOBAL_REGEX_CACHE`] for the patterns matching raw bytes.
+static GLOBAL_BYTES_REGEX_CACHE: LazyLock<Mutex<LruRegexCache<regex::bytes::Regex>>> =
+    LazyLock::new(|| Mutex::new(LruRegexCache::new(DEFAULT_REGEX_CACHE_CAPACITY)));
+
+/// Same as [`GLOBAL_REGEX_CACHE`] for the patterns compiled with `fancy_regex`.
+#[cfg(feature = "fancy-regex")]
+static GLOBAL_FANCY_REGEX_CACHE: LazyLock<Mutex<LruRegexCache<fancy_regex::Regex>>> =
+    LazyLock::new(|| Mutex::new(LruRegexCache::new(DEFAULT_REGEX_CACHE_CAPACITY)));
+
+/// Sets how many compiled patterns the process-wide regex cache keeps, evicting the
+/// least recently used ones beyond that. A capacity of 0 disables the cache.
+///
+/// Every function of this crate looks its pattern up in this cache before compiling it,
+/// so patterns recurring across UDF instances and queries are compiled only once.
+/// Defaults to [`DEFAULT_REGEX_CACHE_CAPACITY`].
+///
+/// # Example
+/// ```
+/// use flarion_task::set_regex_cache_capacity;
+///
+/// set_regex_cache_capacity(1024);
+/// ```
+pub fn set_regex_cache_capacity(capacity: usize) {
+    lock_cache(&GLOBAL_REGEX_CACHE).set_capacity(capacity);
+    lock_cache(&GLOBAL_BYTES_REGEX_CACHE).set_capacity(capacity);
+    #[cfg(feature = "fancy-regex")]
+    lock_cache(&GLOBAL_FANCY_REGEX_CACHE).set_capacity(capacity);
+}
+
+/// Returns `pattern` compiled with `flags` from a process-wide cache, calling `compile`
+/// and caching its result on a miss.
+fn cached_regex<R>(
+    cache: &Mutex<LruRegexCache<R>>,
+    pattern: &str,
+    flags: &str,
+    compile: impl FnOnce() -> Result<R>,
+) -> Result<Arc<R>> {
+    let key = format!("{flags}/{pattern}");
+    if let Some(re) = lock_cache(cache).get(&key) {
+        return Ok(re);
+    }
+
+    // Compile without holding the lock, patterns can take a while to compile
+    #[cfg(feature = "tracing")]
+    let span = tracing::debug_span!("regexp_compile", pattern_len = pattern.len()).entered();
+    let re = Arc::new(compile()?);
+    #[cfg(feature = "tracing")]
+    drop(span);
+    lock_cache(cache).insert(key, Arc::clone(&re));
+    Ok(re)
+}
+
+fn lock_cache<R>(cache: &Mutex<LruRegexCache<R>>) -> MutexGuard<'_, LruRegexCache<R>> {
+    // The cache is valid after any panic, it is never left half updated
+    cache.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// A bounded map from cache key to compiled regex that evicts the least recently used entry.
+///
+/// Eviction scans all entries, which is cheap for the few hundred patterns it holds.
+#[derive(Debug)]
+struct LruRegexCache<R> {
+    capacity: usize,
+    /// Compiled regex and the `clock` value of its last use, per key
+    entries: HashMap<String, (Arc<R>, u64)>,
+    clock: u64,
+}
+
+impl<R> LruRegexCache<R> {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: HashMap::new(),
+            clock: 0,
+        }
+    }
+
+    fn get(&mut self, key: &str) -> Option<Arc<R>> {
+        self.clock += 1;
+        let (re, last_used) = self.entries.get_mut(key)?;
+        *last_used = self.clock;
+        Some(Arc::clone(re))
+    }
+
+    fn insert(&mut self, key: String, re: Arc<R>) {
+        self.clock += 1;
+        self.entries.insert(key, (re, self.clock));
+        self.evict();
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        self.evict();
+    }
+
+    fn evict(&mut self) {
+        while self.entries.len() > self.capacity {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, last_used))| *last_used)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                self.entries.remove(&oldest);
+            }
+        }
+    }
+}
+
+/// Applies an already compiled regex to every row, as described by `config`.
+///
+/// Generic over the input and output arrays so that every string array flavour
+/// (`Utf8`, `LargeUtf8`, `Utf8View`) shares the same semantics. Matched slices are
+/// appended straight into a builder sized from the input, so no `String` is allocated
+/// per row.
+///
+/// Extracts `config.group_index`, rows where the pattern does not match produce the
+/// output chosen by `config.no_match`.
+fn extract_with_regex<'a, A, O>(
+    input: &'a A,
+    re: &ExtractRegex,
+    config: &RegexpExtractConfig,
+) -> Result<GenericStringArray<O>>
+where
+    A: Array,
+    &'a A: IntoIterator<Item = Option<&'a str>>,
+    O: OffsetSizeTrait,
+{
+    // Extracted values are substrings of the input, so its data size is an upper bound
+    extract_with_capacity(input, re, config, string_data_len(input))
+}
+
+/// Same as [`extract_with_regex`], with an output builder sized for `data_capacity` bytes.
+fn extract_with_capacity<'a, A, O>(
+    input: &'a A,
+    re: &ExtractRegex,
+    config: &RegexpExtractConfig,
+    data_capacity: usize,
+) -> Result<GenericStringArray<O>>
+where
+    A: Array,
+    &'a A: IntoIterator<Item = Option<&'a str>>,
+    O: OffsetSizeTrait,
+{
+    #[cfg(feature = "tracing")]
+    let span = tracing::debug_span!(
+        "regexp_extract",
+        rows = input.len(),
+        matches = tracing::field::Empty
+    )
+    .entered();
+    #[cfg(feature = "tracing")]
+    let mut matches = 0;
+
+    let mut builder = GenericStringBuilder::<O>::with_capacity(input.len(), data_capacity);
+    let group_in_range = group_in_range(re, config.group_index);
+    for optional_data in input {
+        let value = match optional_data {
+            Some(data) => match extract_match(re, data, config, group_in_range)? {
+                Some(value) => {
+                    #[cfg(feature = "tracing")]
+                    {
+                        matches += 1;
+                    }
+                    value
+                }
+                None => no_match_value(re, &config.no_match)?,
+            },
+            None => None,
+        };
+        builder.append_option(value);
+    }
+
+    #[cfg(feature = "tracing")]
+    span.record("matches", matches);
+    Ok(builder.finish())
+}
+
+/// Inputs with at least this many rows are split into chunks processed in parallel.
+#[cfg(feature = "rayon")]
+const PARALLEL_THRESHOLD: usize = 64 * 1024;
+
+/// Minimum number of rows processed by a single parallel task.
+#[cfg(feature = "rayon")]
+const PARALLEL_MIN_CHUNK: usize = 16 * 1024;
+
+/// [`extract_with_regex`] for `Utf8` and `LargeUtf8` arrays.
+///
+/// With the `rayon` feature enabled, large inputs are split into chunks that are
+/// extracted on the rayon thread pool and concatenated back in row order. Smaller inputs
+/// stay on the calling thread to avoid the scheduling overhead.
+fn extract_string_array<O: OffsetSizeTrait>(
+    input: &GenericStringArray<O>,
+    re: &ExtractRegex,
+    config: &RegexpExtractConfig,
+) -> Result<GenericStringArray<O>> {
+    #[cfg(feature = "rayon")]
+    if input.len() >= PARALLEL_THRESHOLD {
+        return extract_parallel(input, re, config);
+    }
+
+    extract_with_regex(input, re, config)
+}
+
+/// Parallel path of [`extract_string_array`].
+///
+/// Every chunk is extracted into its own builder by [`extract_with_regex`], and the
+/// finished chunks are concatenated in row order, so the values and the null buffer come
+/// out exactly as on the sequential path.
+#[cfg(feature = "rayon")]
+fn extract_parallel<O: OffsetSizeTrait>(
+    input: &GenericStringArray<O>,
+    re: &ExtractRegex,
+    config: &RegexpExtractConfig,
+) -> Result<GenericStringArray<O>> {
+    use rayon::prelude::*;
+
+    let chunk_size = input
+        .len()
+        .div_ceil(rayon::current_num_threads())
+        .max(PARALLEL_MIN_CHUNK);
+    let offsets: Vec<usize> = (0..input.len()).step_by(chunk_size).collect();
+
+    // Collecting an indexed parallel iterator keeps the chunks in row order, whichever
+    // task finishes first
+    let chunks: Vec<GenericStringArray<O>> = offsets
+        .into_par_iter()
+        .map(|offset| {
+            let chunk = input.slice(offset, chunk_size.min(input.len() - offset));
+            extract_with_regex(&chunk, re, config)
+        })
+        .collect::<Result<_>>()?;
+
+    let chunks: Vec<&dyn Array> = chunks.iter().map(|chunk| chunk as &dyn Array).collect();
+    Ok(concat(&chunks)?
+        .as_any()
+        .downcast_ref::<GenericStringArray<O>>()
+        .unwrap()
+        .clone())
+}
+
+/// Number of bytes of string data referenced by a string array.
+fn string_data_len(input: &dyn Array) -> usize {
+    fn offsets_len<O: OffsetSizeTrait>(input: &dyn Array) -> usize {
+        let offsets = input
+            .as_any()
+            .downcast_ref::<GenericStringArray<O>>()
+            .unwrap()
+            .value_offsets();
+        (offsets[offsets.len() - 1] - offsets[0]).as_usize()
+    }
+
+    match input.data_type() {
+        DataType::Utf8 => offsets_len::<i32>(input),
+        DataType::LargeUtf8 => offsets_len::<i64>(input),
+        DataType::Utf8View => {
+            // The lower 32 bits of a view hold the string length
+            let input = input.as_any().downcast_ref::<StringViewArray>().unwrap();
+            input.views().iter().map(|view| *view as u32 as usize).sum()
+        }
+        _ => 0,
+    }
+}
+
+/// Whether `group_index` names a capture group of `re`, group 0 being the whole match.
+///
+/// Batch loops call this once up front and pass the result to [`extract_match`].
+fn group_in_range(re: &ExtractRegex, group_index: usize) -> bool {
+    group_index < re.captures_len()
+}
+
+/// Extracts from a single non-null string, see [`extract_with_regex`].
+fn extract_value<'a>(
+    re: &ExtractRegex,
+    data: &'a str,
+    config: &'a RegexpExtractConfig,
+    group_in_range: bool,
+) -> Result<Option<Cow<'a, str>>> {
+    match extract_match(re, data, config, group_in_range)? {
+        Some(value) => Ok(value),
+        None => no_match_value(re, &config.no_match),
+    }
+}
+
+/// Same as [`extract_value`], but `None` when the pattern does not match `data`.
+///
+/// `group_in_range` is [`group_in_range`] for `re` and `config.group_index`.
+fn extract_match<'a>(
+    re: &ExtractRegex,
+    data: &'a str,
+    config: &'a RegexpExtractConfig,
+    group_in_range: bool,
+) -> Result<Option<Option<Cow<'a, str>>>> {
+    let group_index = config.group_index;
+    let Some(captures) = extract_captures(re, data)? else {
+        return Ok(None);
+    };
+
+    let group = if group_in_range {
+        captures.get(group_index)
+    } else if spark_compat() {
+        // Like Spark, the index is only checked for rows the pattern matches
+        return Err(RegexpError::InvalidGroupIndex {
+            group_index: group_index as i64,
+            group_count: Some(re.captures_len() - 1),
+            pattern: None,
+        }
+        .into());
+    } else {
+        None
+    };
+    let value = match group {
+        Some(m) => Some(transform_group(m.as_str(), config)),
+        None if config.missing_group_as_no_match => no_match_value(re, &config.no_match)?,
+        None => Some(Cow::Borrowed("")),
+    };
+    Ok(Some(value))
+}
+
+/// Applies the `trim`, `case` and `max_len` options of `config` to an extracted group.
+fn transform_group<'a>(value: &'a str, config: &RegexpExtractConfig) -> Cow<'a, str> {
+    let value = if config.trim {
+        value.trim_ascii()
+    } else {
+        value
+    };
+    let value = match config.case {
+        CaseTransform::None => Cow::Borrowed(value),
+        CaseTransform::Lower => Cow::Owned(value.to_lowercase()),
+        CaseTransform::Upper => Cow::Owned(value.to_uppercase()),
+    };
+    // Case folding can change the number of characters, so the limit is applied last
+    match config
+        .max_len
+        .and_then(|max_len| value.char_indices().nth(max_len))
+    {
+        Some((end, _)) => match value {
+            Cow::Borrowed(value) => Cow::Borrowed(&value[..end]),
+            Cow::Owned(mut value) => {
+                value.truncate(end);
+                Cow::Owned(value)
+            }
+        },
+        None => value,
+    }
+}
+
+/// Output for a row `re` does not match, as chosen by `no_match`.
+fn no_match_value<'a>(
+    re: &ExtractRegex,
+    no_match: &'a NoMatchBehavior,
+) -> Result<Option<Cow<'a, str>>> {
+    match no_match {
+        NoMatchBehavior::EmptyString => Ok(Some(Cow::Borrowed(""))),
+        NoMatchBehavior::Null => Ok(None),
+        NoMatchBehavior::Default(value) => Ok(Some(Cow::Borrowed(value))),
+        // The row itself is left out, as the message may end up in logs
+        NoMatchBehavior::Error => Err(RegexpError::NoMatch {
+            pattern: re.as_str().to_string(),
+        }
+        .into()),
+    }
+}
+
+/// Like [`extract_with_regex`], but every row has its own pattern.
+///
+/// Each distinct pattern is compiled once per batch, a null pattern yields a null row.
+fn extract_with_patterns<'a, T: FromIterator<Option<Cow<'a, str>>>>(
+    input: impl IntoIterator<Item = Option<&'a str>>,
+    patterns: &'a StringArray,
+    flags: &str,
+    config: &'a RegexpExtractConfig,
+) -> Result<T> {
+    let mut compiled: HashMap<&'a str, Arc<ExtractRegex>> = HashMap::new();
+    input
+        .into_iter()
+        .zip(patterns.iter())
+        .map(|(optional_data, optional_pattern)| {
+            let (Some(data), Some(pattern)) = (optional_data, optional_pattern) else {
+                return Ok(None);
+            };
+            let re = match compiled.entry(pattern) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => {
+                    let pattern = config.pattern(pattern);
+                    entry.insert(compile_extract_regex("regexp_extract", &pattern, flags)?)
+                }
+            };
+            extract_value(re, data, config, group_in_range(re, config.group_index))
+        })
+        .collect()
+}
+
+/// Extracts a capture group from strings, optionally returning null when nothing matches.
+///
+/// # Arguments
+/// * `input` - Input string array to process
+/// * `pattern` - Regular expression pattern to match
+/// * `group_index` - Index of the capture group to extract (0 for full match)
+/// * `null_on_no_match` - If true, rows where the pattern does not match yield null
+///   instead of an empty string. A match whose group did not participate still yields `""`.
+///
+/// # Returns
+/// * `Result<ArrayRef>` - Arrow array containing extracted strings or nulls
+///
+/// # Example
+/// ```
+/// use flarion_task::regexp_extract_opt;
+/// use datafusion::arrow::array::{Array, StringArray};
+///
+/// let input = StringArray::from(vec![Some("hello123"), Some("hello")]);
+/// let result = regexp_extract_opt(&input, r"([a-z]+)(\d+)", 1, true).unwrap();
+/// let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+///
+/// assert_eq!(result_array.value(0), "hello");
+/// assert!(result_array.is_null(1));
+/// ```
+pub fn regexp_extract_opt(
+    input: &StringArray,
+    pattern: &str,
+    group_index: usize,
+    null_on_no_match: bool,
+) -> Result<ArrayRef> {
+    let no_match = if null_on_no_match {
+        NoMatchBehavior::Null
+    } else {
+        NoMatchBehavior::EmptyString
+    };
+    let config = RegexpExtractConfig {
+        group_index,
+        no_match,
+        ..Default::default()
+    };
+    regexp_extract_with_config(input, pattern, &config)
+}
+
+/// Extracts a capture group from strings, returning `default` when nothing matches.
+///
+/// # Arguments
+/// * `input` - Input string array to process
+/// * `pattern` - Regular expression pattern to match
+/// * `group_index` - Index of the capture group to extract (0 for full match)
+/// * `default` - Value for rows where the pattern does not match
+///
+/// # Returns
+/// * `Result<ArrayRef>` - Arrow array containing extracted strings or nulls.
+///   Use [`regexp_extract_with_config`] with `missing_group_as_no_match` to also apply
+///   the default when the group did not participate in the match.
+///
+/// # Example
+/// ```
+/// use flarion_task::regexp_extract_default;
+/// use datafusion::arrow::array::{Array, StringArray};
+///
+/// let input = StringArray::from(vec![Some("id=42"), Some("none"), None]);
+/// let result = regexp_extract_default(&input, r"id=(\d+)", 1, "UNKNOWN").unwrap();
+/// let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+///
+/// assert_eq!(result_array.value(0), "42");
+/// assert_eq!(result_array.value(1), "UNKNOWN");
+/// assert!(result_array.is_null(2));
+/// ```
+pub fn regexp_extract_default(
+    input: &StringArray,
+    pattern: &str,
+    group_index: usize,
+    default: &str,
+) -> Result<ArrayRef> {
+    let config = RegexpExtractConfig {
+        group_index,
+        no_match: NoMatchBehavior::Default(default.to_string()),
+        ..Default::default()
+    };
+    regexp_extract_with_config(input, pattern, &config)
+}
+
+/// Creates a DataFusion UDF that extracts a capture group from strings, with a fallback for rows without a match.
+///
+/// # Returns
+/// * `ScalarUDF` - User Defined Function that accepts:
+///   - input: string array to process
+///   - pattern: regex pattern string
+///   - group_index: capture group index (as UInt32)
+///   - default: value for rows where the pattern does not match.
+pub fn create_regexp_extract_default() -> ScalarUDF {
+    regex_udf!(
+        "regexp_extract_default" -> DataType::Utf8,
+        |input, pattern, group_index: UInt32, default: Utf8|
+            regexp_extract_default(input, pattern, group_index, default)
+    )
+}
+
+/// Extracts a capture group from strings that match a regular expression pattern in full.
+///
+/// The pattern is anchored at both ends, so a row where only a part of the string matches
+/// yields an empty string, like a row without any match. Users do not need to add `^` and
+/// `$` themselves.
+///
+/// # Arguments
+/// * `input` - Input string array to process
+/// * `pattern` - Regular expression pattern the whole string must match
+/// * `group_index` - Index of the capture group to extract (0 for full match)
+///
+/// # Returns
+/// * `Result<ArrayRef>` - Arrow array containing extracted strings or nulls
+///
+/// # Example
+/// ```
+/// use flarion_task::regexp_extract_anchored;
+/// use datafusion::arrow::array::{Array, StringArray};
+///
+/// let input = StringArray::from(vec![Some("978-3"), Some("978-3-x")]);
+/// let result = regexp_extract_anchored(&input, r"(\d{3})-(\d)", 1).unwrap();
+/// let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+///
+/// assert_eq!(result_array.value(0), "978");
+/// assert_eq!(result_array.value(1), "");
+/// ```
+pub fn regexp_extract_anchored(
+    input: &StringArray,
+    pattern: &str,
+    group_index: usize,
+) -> Result<ArrayRef> {
+    // Compiling the pattern on its own first reports errors against the pattern as written,
+    // and ensures it cannot close the wrapping group early
+    compile_extract_regex("regexp_extract_anchored", pattern, "")?;
+    let re = compile_extract_regex(
+        "regexp_extract_anchored",
+        &format!(r"\A(?:{pattern})\z"),
+        "",
+    )?;
+
+    let array: StringArray =
+        extract_string_array(input, &re, &RegexpExtractConfig::new(group_index))?;
+
+    Ok(Arc::new(array))
+}
+
+/// Extracts a capture group from strings, returning an error if `group_index` does not
+/// exist in the pattern instead of silently producing empty strings.
+///
+/// # Arguments
+/// * `input` - Input string array to process
+/// * `pattern` - Regular expression pattern to match
+/// * `group_index` - Index of the capture group to extract (0 for full match)
+///
+/// # Returns
+/// * `Result<ArrayRef>` - Arrow array containing extracted strings or nulls.
+///   Returns an error naming the maximum valid index when `group_index` is out of range.
+///
+/// # Example
+/// ```
+/// use flarion_task::regexp_extract_strict;
+/// use datafusion::arrow::array::StringArray;
+///
+/// let input = StringArray::from(vec![Some("hello123")]);
+/// assert!(regexp_extract_strict(&input, r"([a-z]+)(\d+)", 2).is_ok());
+/// assert!(regexp_extract_strict(&input, r"([a-z]+)(\d+)", 3).is_err());
+/// ```
+pub fn regexp_extract_strict(
+    input: &StringArray,
+    pattern: &str,
+    group_index: usize,
+) -> Result<ArrayRef> {
+    let re = compile_extract_regex("regexp_extract_strict", pattern, "")?;
+    check_group_index(&re, group_index)?;
+
+    let array: StringArray =
+        extract_string_array(input, &re, &RegexpExtractConfig::new(group_index))?;
+
+    Ok(Arc::new(array))
+}
+
+/// Extracts a capture group from the leftmost-longest match, with POSIX semantics.
+///
+/// The `regex` engine is leftmost-first: among the matches starting at the leftmost
+/// position it picks the one preferred by the pattern, so `(foo|foobar)` matches `foo` in
+/// `"foobar"`. This function picks the longest one instead, `foobar`, regardless of the
+/// order of the alternatives. The groups are then those of the leftmost-first match of
+/// that exact span.
+///
+/// The longest match is found with a lazy DFA, which cannot handle every pattern: Unicode
+/// word boundaries (`\b` without `(?-u)`) are rejected with an error. It always uses the
+/// `regex` engine, also with the `fancy-regex` feature, within the size limits and mode
+/// of [`set_regexp_options`].
+///
+/// # Arguments
+/// * `input` - Input string array to process
+/// * `pattern` - Regular expression pattern to match
+/// * `group_index` - Index of the capture group to extract (0 for full match)
+///
+/// # Returns
+/// * `Result<ArrayRef>` - Arrow array containing extracted strings or nulls
+///
+/// # Example
+/// ```
+/// use flarion_task::regexp_extract_longest;
+/// use datafusion::arrow::array::{AsArray, StringArray};
+///
+/// let input = StringArray::from(vec!["foobar"]);
+/// let result = regexp_extract_longest(&input, "(foo|foobar)", 1).unwrap();
+/// assert_eq!(result.as_string::<i32>().value(0), "foobar");
+/// ```
+pub fn regexp_extract_longest(
+    input: &StringArray,
+    pattern: &str,
+    group_index: usize,
+) -> Result<ArrayRef> {
+    use regex_automata::hybrid::dfa::DFA;
+    use regex_automata::nfa::thompson::{self, pikevm::PikeVM};
+    use regex_automata::{util::syntax, Anchored, Input, MatchKind};
+
+    let re = compile_regex("regexp_extract_longest", pattern)?;
+    let options = regexp_options();
+    let syntax = syntax::Config::new().unicode(options.unicode);
+    let nfa = thompson::Config::new().nfa_size_limit(Some(options.size_limit));
+    // Without preferences between alternatives, an anchored search only stops when no
+    // longer match is possible and reports the end of the longest one
+    let longest = DFA::builder()
+        .configure(
+            DFA::config()
+                .match_kind(MatchKind::All)
+                .cache_capacity(options.dfa_size_limit),
+        )
+        .syntax(syntax)
+        .thompson(nfa.clone())
+        .build(pattern)
+        .map_err(|e| invalid_pattern("regexp_extract_longest", pattern, e))?;
+    let mut cache = longest.create_cache();
+    // Searched over the span of the longest match, the match state is held by the most
+    // preferred thread that reaches it, so the groups are those of the leftmost-first
+    // match of that span. Unlike a cut-off haystack, the span leaves the look-around
+    // assertions such as `$` to see the rest of the string.
+    let groups = PikeVM::builder()
+        .configure(PikeVM::config().match_kind(MatchKind::All))
+        .syntax(syntax)
+        .thompson(nfa)
+        .build(pattern)
+        .map_err(|e| invalid_pattern("regexp_extract_longest", pattern, e))?;
+    let mut groups_cache = groups.create_cache();
+    let mut captures = groups.create_captures();
+
+    let mut builder = StringBuilder::with_capacity(input.len(), string_data_len(input));
+    for optional_data in input.iter() {
+        let Some(data) = optional_data else {
+            builder.append_null();
+            continue;
+        };
+        // Both semantics agree on where the leftmost match starts
+        let Some(start) = re.find(data).map(|m| m.start()) else {
+            builder.append_value("");
+            continue;
+        };
+        let search = Input::new(data).range(start..).anchored(Anchored::Yes);
+        let end = longest
+            .try_search_fwd(&mut cache, &search)
+            .map_err(|e| match_failed("regexp_extract_longest", pattern, e))?
+            .map_or(start, |m| m.offset());
+        let span = Input::new(data).span(start..end).anchored(Anchored::Yes);
+        groups.search(&mut groups_cache, &span, &mut captures);
+        let value = captures
+            .get_group(group_index)
+            .map_or("", |group| &data[group.range()]);
+        builder.append_value(value);
+    }
+
+    Ok(Arc::new(builder.finish()))
+}
+
+/// Returns an error if `group_index` is not a capture group of `re`.
+fn check_group_index(re: &ExtractRegex, group_index: usize) -> Result<()> {
+    let max_index = re.captures_len() - 1;
+    if group_index > max_index {
+        return Err(RegexpError::InvalidGroupIndex {
+            group_index: group_index as i64,
+            group_count: Some(max_index),
+            pattern: Some(re.as_str().to_string()),
+        }
+        .into());
+    }
+    Ok(())
+}
+
+/// Patterns longer than this many characters are truncated in error messages.
+const MAX_PATTERN_DISPLAY_LEN: usize = 120;
+
+/// Compiles `pattern`, naming the function and the pattern in the error on failure.
+///
+/// The compiled regex is shared through the process-wide cache, see
+/// [`set_regex_cache_capacity`].
+fn compile_regex(udf_name: &str, pattern: &str) -> Result<Arc<Regex>> {
+    cached_regex(&GLOBAL_REGEX_CACHE, pattern, "", || {
+        regex_builder(pattern, &regexp_options())
+            .build()
+            .map_err(|e| invalid_pattern(udf_name, pattern, e))
+    })
+}
+
+/// Default for [`RegexpOptions::size_limit`], the same as the regex crate's.
+pub const DEFAULT_REGEX_SIZE_LIMIT: usize = 10 * (1 << 20);
+
+/// Default for [`RegexpOptions::dfa_size_limit`], the same as the regex crate's.
+pub const DEFAULT_REGEX_DFA_SIZE_LIMIT: usize = 2 * (1 << 20);
+
+/// Default for [`RegexpOptions::backtrack_limit`], the same as fancy-regex's.
+pub const DEFAULT_REGEX_BACKTRACK_LIMIT: usize = 1_000_000;
+
+/// Limits and modes applied when compiling patterns, set with [`set_regexp_options`].
+///
+/// They bound the memory a single user supplied pattern can take, e.g. a huge bounded
+/// repetition like `a{1000000}` fails to compile instead of allocating gigabytes, and
+/// with the `fancy-regex` feature the time a single match can take.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RegexpOptions {
+    /// Approximate maximum size in bytes of a compiled regex
+    pub size_limit: usize,
+    /// Approximate maximum size in bytes of the cache of the lazy DFA used for matching
+    pub dfa_size_limit: usize,
+    /// Maximum number of backtracking steps of a single match with the `fancy-regex`
+    /// feature, a match exceeding it fails with an error instead of running for hours
+    /// on adversarial input. The default `regex` engine matches in linear time and
+    /// ignores it.
+    pub backtrack_limit: usize,
+    /// Unicode mode, on by default. Turning it off gives every pattern `(?-u)`
+    /// semantics, which is faster on ASCII data: `\w`, `\d`, `\s` and `\b` only match
+    /// ASCII, and case insensitive matching only folds ASCII letters. Since the input is
+    /// UTF-8, a pattern that could then match part of a multi-byte character, such as
+    /// `.` or `[^a]`, fails to compile, use [`regexp_extract_binary`] to match raw bytes,
+    /// where such a pattern matches any single byte.
+    /// The `fancy-regex` engine has no byte mode and ignores it.
+    pub unicode: bool,
+}
+
+impl Default for RegexpOptions {
+    fn default() -> Self {
+        DEFAULT_REGEXP_OPTIONS
+    }
+}
+
+const DEFAULT_REGEXP_OPTIONS: RegexpOptions = RegexpOptions {
+    size_limit: DEFAULT_REGEX_SIZE_LIMIT,
+    dfa_size_limit: DEFAULT_REGEX_DFA_SIZE_LIMIT,
+    backtrack_limit: DEFAULT_REGEX_BACKTRACK_LIMIT,
+    unicode: true,
+};
+
+static REGEXP_OPTIONS: RwLock<RegexpOptions> = RwLock::new(DEFAULT_REGEXP_OPTIONS);
+
+/// Sets the limits every function of this crate compiles patterns with.
+///
+/// Patterns compiled with the previous limits are dropped from the process-wide cache.
+/// A pattern exceeding the limits makes the function return an error.
+///
+/// # Example
+/// ```
+/// use flarion_task::{set_regexp_options, RegexpOptions};
+///
+/// set_regexp_options(RegexpOptions {
+///     size_limit: 1 << 20,
+///     ..Default::default()
+/// });
+/// ```
+pub fn set_regexp_options(options: RegexpOptions) {
+    *REGEXP_OPTIONS
+        .write()
+        .unwrap_or_else(PoisonError::into_inner) = options;
+    lock_cache(&GLOBAL_REGEX_CACHE).clear();
+    lock_cache(&GLOBAL_BYTES_REGEX_CACHE).clear();
+    #[cfg(feature = "fancy-regex")]
+    lock_cache(&GLOBAL_FANCY_REGEX_CACHE).clear();
+}
+
+fn regexp_options() -> RegexpOptions {
+    *REGEXP_OPTIONS
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+}
+
+static SPARK_COMPAT: AtomicBool = AtomicBool::new(false);
+
+/// Enables or disables Spark compatibility for the edge cases where this crate is more
+/// permissive than Spark 3.5's `regexp_extract`. Disabled by default.
+///
+/// When enabled:
+///
+/// * A group index beyond the number of groups of the pattern fails with Spark's
+///   `Regex group count is N, but the specified group index is M` error instead of
+///   yielding an empty string. As in Spark, only rows the pattern matches are checked,
+///   so a batch where nothing matches still succeeds.
+///
+/// Behaviour that already matches Spark either way: null input or pattern yields null,
+/// no match and a group that did not participate yield an empty string, and a negative
+/// group index is an error. Patterns use the syntax of the `regex` crate, which lacks
+/// Java features such as look-around and backreferences, regardless of this setting.
+///
+/// # Example
+/// ```
+/// use flarion_task::{regexp_extract, set_spark_compat};
+/// use datafusion::arrow::array::StringArray;
+///
+/// set_spark_compat(true);
+/// let input = StringArray::from(vec!["abc123"]);
+/// assert!(regexp_extract(&input, r"(\d+)", 2).is_err());
+/// ```
+pub fn set_spark_compat(enabled: bool) {
+    SPARK_COMPAT.store(enabled, Ordering::Relaxed);
+}
+
+/// Returns whether Spark compatibility is enabled, see [`set_spark_compat`].
+pub fn spark_compat() -> bool {
+    SPARK_COMPAT.load(Ordering::Relaxed)
+}
+
+/// Returns a builder for `pattern` with the limits and Unicode mode from `options` applied.
+fn regex_builder(pattern: &str, options: &RegexpOptions) -> RegexBuilder {
+    let mut builder = RegexBuilder::new(pattern);
+    builder
+        .size_limit(options.size_limit)
+        .dfa_size_limit(options.dfa_size_limit)
+        .unicode(options.unicode);
+    builder
+}
+
+/// Compiles a regular expression pattern the same way the functions and UDFs of this crate do.
+///
+/// The regex comes from the process-wide cache, so compiling a pattern here also
+/// saves the compilation when a query later uses it. `Regex` is `Send + Sync`, the
+/// returned `Arc<Regex>` can be shared across threads freely.
+///
+/// This is always a [`regex::Regex`], also with the `fancy-regex` feature, which only
+/// changes the engine of the `regexp_extract` family of functions.
+///
+/// # Arguments
+/// * `pattern` - Regular expression pattern to compile
+///
+/// # Returns
+/// * `Result<Arc<Regex>>` - The compiled regex, or the error `regexp_extract` reports
+///   for the pattern
+///
+/// # Example
+/// ```
+/// use flarion_task::compile_pattern;
+///
+/// let re = compile_pattern(r"([a-z]+)(\d+)").unwrap();
+/// assert!(re.is_match("hello123"));
+///
+/// let err = compile_pattern(r"([a-z]+").unwrap_err();
+/// assert!(err.to_string().contains("invalid pattern"));
+/// ```
+pub fn compile_pattern(pattern: &str) -> Result<Arc<Regex>> {
+    compile_regex("regexp_extract", pattern)
+}
+
+/// Checks that a regular expression pattern compiles for [`regexp_extract`].
+///
+/// # Arguments
+/// * `pattern` - Regular expression pattern to check
+///
+/// # Returns
+/// * `Result<()>` - Ok if the pattern is valid, otherwise the compile error
+///
+/// # Example
+/// ```
+/// use flarion_task::validate_pattern;
+///
+/// assert!(validate_pattern(r"(\d+)").is_ok());
+/// assert!(validate_pattern(r"(\d+").is_err());
+/// ```
+pub fn validate_pattern(pattern: &str) -> Result<()> {
+    compile_extract_regex("regexp_extract", pattern, "").map(|_| ())
+}
+
+/// Returns the number of explicit capture groups of a regular expression pattern.
+///
+/// The implicit group 0 (the full match) is not counted, so valid group indices for the
+/// pattern are `0..=group_count(pattern)`.
+///
+/// # Arguments
+/// * `pattern` - Regular expression pattern to inspect
+///
+/// # Returns
+/// * `Result<usize>` - Number of capture groups, or the compile error of [`compile_pattern`]
+///
+/// # Example
+/// ```
+/// use flarion_task::group_count;
+///
+/// assert_eq!(group_count(r"([a-z]+)(?:-)(\d+)").unwrap(), 2);
+/// assert_eq!(group_count(r"\d+").unwrap(), 0);
+/// ```
+pub fn group_count(pattern: &str) -> Result<usize> {
+    Ok(compile_extract_regex("regexp_extract", pattern, "")?.captures_len() - 1)
+}
+
+/// Returns the names of the named capture groups of a regular expression pattern.
+///
+/// Names are in group index order, unnamed groups are skipped.
+///
+/// # Arguments
+/// * `pattern` - Regular expression pattern to inspect
+///
+/// # Returns
+/// * `Result<Vec<String>>` - Group names, or the compile error of [`compile_pattern`]
+///
+/// # Example
+/// ```
+/// use flarion_task::named_groups;
+///
+/// let names = named_groups(r"(?P<year>\d{4})-(\d{2})-(?P<day>\d{2})").unwrap();
+/// assert_eq!(names, ["year", "day"]);
+/// ```
+pub fn named_groups(pattern: &str) -> Result<Vec<String>> {
+    Ok(compile_extract_regex("regexp_extract", pattern, "")?
+        .capture_names()
+        .flatten()
+        .map(|name| name.to_string())
+        .collect())
+}
+
+/// Builds the error returned when `pattern` fails to compile, e.g.
+/// `regexp_extract: invalid pattern "(a": <regex error>`.
+fn invalid_pattern(udf_name: &str, pattern: &str, error: impl Display) -> DataFusionError {
+    RegexpError::InvalidPattern {
+        function: udf_name.to_string(),
+        pattern: pattern.to_string(),
+        reason: error.to_string(),
+    }
+    .into()
+}
+
+/// Errors of the functions and UDFs of this crate that callers may want to tell apart.
+///
+/// They are returned as [`DataFusionError::External`], so SQL users see the message while
+/// Rust callers can recover the variant with [`RegexpError::find`].
+///
+/// # Example
+/// ```
+/// use flarion_task::{regexp_extract, RegexpError};
+/// use datafusion::arrow::array::StringArray;
+///
+/// let input = StringArray::from(vec!["abc"]);
+/// let err = regexp_extract(&input, "(abc", 1).unwrap_err();
+/// assert!(matches!(
+///     RegexpError::find(&err),
+///     Some(RegexpError::InvalidPattern { pattern, .. }) if pattern == "(abc"
+/// ));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegexpError {
+    /// A pattern that does not compile.
+    InvalidPattern {
+        /// Function the pattern was given to
+        function: String,
+        /// The pattern as given, truncated in the message when it is long
+        pattern: String,
+        /// Why the pattern does not compile, as reported by the regex engine
+        reason: String,
+    },
+    /// A group index that is negative or not a capture group of the pattern.
+    InvalidGroupIndex {
+        /// The group index as given
+        group_index: i64,
+        /// Number of capture groups of the pattern, `None` for a negative index
+        group_count: Option<usize>,
+        /// The pattern, if it is part of the message
+        pattern: Option<String>,
+    },
+    /// An input of a type the function does not support.
+    UnsupportedInputType {
+        /// Function the input was given to
+        function: String,
+        /// Description of the supported types
+        expected: String,
+        /// Type of the input
+        actual: DataType,
+        /// What to use instead, if there is an alternative
+        hint: Option<String>,
+    },
+    /// A regex flag that is not supported.
+    InvalidFlag {
+        /// Function the flags were given to
+        function: String,
+        /// The first unsupported flag
+        flag: char,
+    },
+    /// A group name that is not a named capture group of the pattern.
+    UnknownGroupName {
+        /// Function the group name was given to
+        function: String,
+        /// The group name as given
+        group_name: String,
+        /// The pattern the group was looked up in
+        pattern: String,
+    },
+    /// A numeric argument below its minimum, such as a position before the first
+    /// character.
+    ArgumentOutOfRange {
+        /// Function the argument was given to
+        function: String,
+        /// Name of the argument
+        argument: String,
+        /// The argument as given
+        value: i64,
+        /// Smallest accepted value
+        min: i64,
+    },
+    /// A row the pattern does not match, with [`NoMatchBehavior::Error`]. The row is
+    /// left out so that the error does not leak data into logs.
+    NoMatch {
+        /// The pattern that did not match
+        pattern: String,
+    },
+    /// A match the regex engine gave up on, e.g. after too much backtracking.
+    MatchFailed {
+        /// Function the pattern was given to
+        function: String,
+        /// The pattern
+        pattern: String,
+        /// Why matching failed, as reported by the regex engine
+        reason: String,
+    },
+    /// A character offset or list offset too large for the `Int32` it is returned as.
+    OffsetOverflow {
+        /// Function computing the offset
+        function: String,
+        /// The offset
+        offset: usize,
+    },
+    /// A UDF invoked with too few or too many arguments.
+    ArityMismatch {
+        /// Name of the UDF
+        function: String,
+        /// Minimum number of arguments
+        min: usize,
+        /// Maximum number of arguments
+        max: usize,
+        /// Number of arguments it was invoked with
+        actual: usize,
+    },
+}
+
+impl RegexpError {
+    /// Returns the `RegexpError` behind `err`, if it was raised by this crate.
+    pub fn find(err: &DataFusionError) -> Option<&RegexpError> {
+        let mut source: Option<&(dyn std::error::Error + 'static)> = Some(err);
+        while let Some(err) = source {
+            if let Some(err) = err.downcast_ref::<RegexpError>() {
+                return Some(err);
+            }
+            source = err.source();
+        }
+        None
+    }
+}
+
+impl Display for RegexpError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RegexpError::InvalidPattern {
+                function,
+                pattern,
+                reason,
+            } => {
+                let shown = match pattern.char_indices().nth(MAX_PATTERN_DISPLAY_LEN) {
+                    Some((end, _)) => format!("{}...", &pattern[..end]),
+                    None => pattern.to_string(),
+                };
+                write!(f, "{function}: invalid pattern \"{shown}\": {reason}")
+            }
+            RegexpError::InvalidGroupIndex {
+                group_index,
+                group_count: None,
+                ..
+            } => write!(f, "group_index must be non-negative, got {group_index}"),
+            RegexpError::InvalidGroupIndex {
+                group_index,
+                group_count: Some(group_count),
+                pattern: Some(pattern),
+            } => write!(
+                f,
+                "group_index {group_index} is out of range, pattern '{pattern}' has {group_count} capture groups (max valid index is {group_count})"
+            ),
+            // Same message as Spark
+            RegexpError::InvalidGroupIndex {
+                group_index,
+                group_count: Some(group_count),
+                pattern: None,
+            } => write!(
+                f,
+                "Regex group count is {group_count}, but the specified group index is {group_index}"
+            ),
+            RegexpError::UnsupportedInputType {
+                function,
+                expected,
+                actual,
+                hint,
+            } => {
+                write!(f, "{function} expects {expected}, got {actual}")?;
+                match hint {
+                    Some(hint) => write!(f, ". {hint}"),
+                    None => Ok(()),
+                }
+            }
+            RegexpError::InvalidFlag { function, flag } => write!(
+                f,
+                "{function}: invalid regex flag '{flag}', supported flags are {SUPPORTED_FLAGS}"
+            ),
+            RegexpError::UnknownGroupName {
+                function,
+                group_name,
+                pattern,
+            } => write!(
+                f,
+                "{function}: pattern '{pattern}' has no capture group named '{group_name}'"
+            ),
+            RegexpError::ArgumentOutOfRange {
+                function,
+                argument,
+                value,
+                min,
+            } => write!(f, "{function}: {argument} must be at least {min}, got {value}"),
+            RegexpError::NoMatch { pattern } => {
+                write!(f, "Pattern '{pattern}' did not match a row")
+            }
+            RegexpError::MatchFailed {
+                function,
+                pattern,
+                reason,
+            } => write!(f, "{function}: pattern '{pattern}' failed to match: {reason}"),
+            RegexpError::OffsetOverflow { function, offset } => {
+                write!(f, "{function}: offset {offset} does not fit in an Int32")
+            }
+            RegexpError::ArityMismatch {
+                function,
+                min,
+                max,
+                actual,
+            } => {
+                if min == max {
+                    write!(f, "{function} expects {min} arguments, got {actual}")
+                } else {
+                    write!(f, "{function} expects {min} to {max} arguments, got {actual}")
+                }
+            }
+        }
+    }
+}
+
+impl std::error::Error for RegexpError {}
+
+/// The flags accepted by [`check_flags`], as listed in [`RegexpError::InvalidFlag`].
+#[cfg(not(feature = "fancy-regex"))]
+const SUPPORTED_FLAGS: &str = "'i', 'm', 's', 'x', 'R' and 'U'";
+#[cfg(feature = "fancy-regex")]
+const SUPPORTED_FLAGS: &str = "'i', 'm', 's', 'x' and 'U'";
+
+/// Error for a match `udf_name` gave up on.
+fn match_failed(udf_name: &str, pattern: &str, reason: impl Display) -> DataFusionError {
+    RegexpError::MatchFailed {
+        function: udf_name.to_string(),
+        pattern: pattern.to_string(),
+        reason: reason.to_string(),
+    }
+    .into()
+}
+
+impl From<RegexpError> for DataFusionError {
+    fn from(err: RegexpError) -> Self {
+        DataFusionError::External(Box::new(err))
+    }
+}
+
+/// Compiles a pattern with flags given as a string of single-character options:
+///
+/// * `i` - case-insensitive matching
+/// * `m` - multi-line mode, `^` and `$` match at line boundaries
+/// * `s` - `.` also matches `\n`
+/// * `x` - verbose mode, whitespace is ignored and `#` starts a comment
+/// * `R` - CRLF mode, with `m` the anchors also treat `\r\n` as a line boundary, so
+///   `$` matches before the `\r`. Not supported with the `fancy-regex` feature
+/// * `U` - swap the meaning of greedy and lazy quantifiers
+fn build_regex(pattern: &str, flags: &str) -> Result<Arc<ExtractRegex>> {
+    check_flags("regexp_extract_flags", flags)?;
+    compile_extract_regex("regexp_extract_flags", pattern, flags)
+}
+
+/// Returns an error naming the first flag of `flags` that [`build_regex`] does not support.
+fn check_flags(udf_name: &str, flags: &str) -> Result<()> {
+    if let Some(flag) = flags.chars().find(|flag| !"imsxRU".contains(*flag)) {
+        return Err(RegexpError::InvalidFlag {
+            function: udf_name.to_string(),
+            flag,
+        }
+        .into());
+    }
+    Ok(())
+}
+
+/// Regex engine behind the `regexp_extract` family of functions.
+///
+/// This is [`regex::Regex`], or `fancy_regex::Regex` with the `fancy-regex` feature.
+#[cfg(not(feature = "fancy-regex"))]
+type ExtractRegex = Regex;
+#[cfg(feature = "fancy-regex")]
+type ExtractRegex = fancy_regex::Regex;
+
+#[cfg(not(feature = "fancy-regex"))]
+type ExtractCaptures<'h> = regex::Captures<'h>;
+#[cfg(feature = "fancy-regex")]
+type ExtractCaptures<'h> = fancy_regex::Captures<'h>;
+
+/// Compiles a pattern for the `regexp_extract` family, with flags already checked by
+/// [`build_regex`].
+#[cfg(not(feature = "fancy-regex"))]
+fn compile_extract_regex(udf_name: &str, pattern: &str, flags: &str) -> Result<Arc<ExtractRegex>> {
+    compile_regex_with_flags(udf_name, pattern, flags)
+}
+
+/// [`compile_regex`] with any of the `i`, `m`, `s`, `x`, `R` and `U` flags, already checked by
+/// the caller.
+fn compile_regex_with_flags(udf_name: &str, pattern: &str, flags: &str) -> Result<Arc<Regex>> {
+    cached_regex(&GLOBAL_REGEX_CACHE, pattern, flags, || {
+        let mut builder = regex_builder(pattern, &regexp_options());
+        for flag in flags.chars() {
+            match flag {
+                'i' => builder.case_insensitive(true),
+                'm' => builder.multi_line(true),
+                's' => builder.dot_matches_new_line(true),
+                'x' => builder.ignore_whitespace(true),
+                'R' => builder.crlf(true),
+                _ => builder.swap_greed(true),
+            };
+        }
+        builder
+            .build()
+            .map_err(|e| invalid_pattern(udf_name, pattern, e))
+    })
+}
+
+/// Compiles a pattern for the `regexp_extract` family, with flags already checked by
+/// [`build_regex`].
+#[cfg(feature = "fancy-regex")]
+fn compile_extract_regex(udf_name: &str, pattern: &str, flags: &str) -> Result<Arc<ExtractRegex>> {
+    cached_regex(&GLOBAL_FANCY_REGEX_CACHE, pattern, flags, || {
+        if flags.contains('R') {
+            return Err(RegexpError::InvalidFlag {
+                function: udf_name.to_string(),
+                flag: 'R',
+            }
+            .into());
+        }
+        let options = regexp_options();
+        // fancy-regex has no builder methods for the flags, they are set inline instead
+        let flagged;
+        let source = if flags.is_empty() {
+            pattern
+        } else {
+            flagged = format!("(?{flags}){pattern}");
+            &flagged
+        };
+        fancy_regex::RegexBuilder::new(source)
+            .delegate_size_limit(options.size_limit)
+            .delegate_dfa_size_limit(options.dfa_size_limit)
+            .backtrack_limit(options.backtrack_limit)
+            .build()
+            .map_err(|e| invalid_pattern(udf_name, pattern, e))
+    })
+}
+
+/// Returns the captures of the leftmost match of `re` in `data`.
+#[cfg(not(feature = "fancy-regex"))]
+fn extract_captures<'h>(re: &ExtractRegex, data: &'h str) -> Result<Option<ExtractCaptures<'h>>> {
+    Ok(re.captures(data))
+}
+
+/// Returns the captures of the leftmost match of `re` in `data`, failing when matching
+/// gives up, e.g. after too much backtracking.
+#[cfg(feature = "fancy-regex")]
+fn extract_captures<'h>(re: &ExtractRegex, data: &'h str) -> Result<Option<ExtractCaptures<'h>>> {
+    re.captures(data)
+        .map_err(|e| match_failed("regexp_extract", re.as_str(), e))
+}
+
+/// Extracts a capture group from strings using a regular expression pattern and flags.
+///
+/// # Arguments
+/// * `input` - Input string array to process
+/// * `pattern` - Regular expression pattern to match
+/// * `group_index` - Index of the capture group to extract (0 for full match)
+/// * `flags` - Any combination of `i` (case-insensitive), `m` (multi-line),
+///   `s` (dot matches newline), `x` (verbose: whitespace is ignored and `#` starts a
+///   comment up to the end of the line, so a literal space or `#` must be escaped) and
+///   `U` (swap greed), plus `R` (CRLF: with `m`, `^` and `$` also treat `\r\n` as a line
+///   boundary) with the default engine
+///
+/// # Returns
+/// * `Result<ArrayRef>` - Arrow array containing extracted strings or nulls.
+///   Returns an error on unknown flag characters.
+///
+/// # Example
+/// ```
+/// use flarion_task::regexp_extract_flags;
+/// use datafusion::arrow::array::{Array, StringArray};
+///
+/// let input = StringArray::from(vec![Some("HELLO123")]);
+/// let result = regexp_extract_flags(&input, r"([a-z]+)(\d+)", 1, "i").unwrap();
+/// let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+///
+/// assert_eq!(result_array.value(0), "HELLO");
+/// ```
+pub fn regexp_extract_flags(
+    input: &StringArray,
+    pattern: &str,
+    group_index: usize,
+    flags: &str,
+) -> Result<ArrayRef> {
+    let re = build_regex(pattern, flags)?;
+
+    let array: StringArray =
+        extract_string_array(input, &re, &RegexpExtractConfig::new(group_index))?;
+
+    Ok(Arc::new(array))
+}
+
+/// A pattern compiled once for repeated extraction.
+///
+/// The functions taking a pattern string look it up in the process-wide cache on every
+/// call, which hashes the pattern. In a loop over many arrays, compile the pattern once
+/// and reuse the extractor instead. It is cheap to clone and can be shared across threads.
+///
+/// # Example
+/// ```
+/// use flarion_task::CompiledExtractor;
+/// use datafusion::arrow::array::{AsArray, StringArray};
+///
+/// let extractor = CompiledExtractor::new(r"([a-z]+)(\d+)", "i").unwrap();
+/// for batch in [vec!["abc123"], vec!["XYZ9", "none"]] {
+///     let result = extractor.extract(&StringArray::from(batch), 2).unwrap();
+///     assert!(result.as_string::<i32>().value(0).ends_with(|c: char| c.is_ascii_digit()));
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct CompiledExtractor {
+    re: Arc<ExtractRegex>,
+}
+
+impl CompiledExtractor {
+    /// Compiles `pattern` with `flags` as accepted by [`regexp_extract_flags`], returning
+    /// the same errors for an invalid pattern or flag.
+    pub fn new(pattern: &str, flags: &str) -> Result<Self> {
+        Ok(Self {
+            re: build_regex(pattern, flags)?,
+        })
+    }
+
+    /// Same as [`regexp_extract`] with the compiled pattern.
+    pub fn extract(&self, input: &StringArray, group_index: usize) -> Result<ArrayRef> {
+        let array: StringArray =
+            extract_string_array(input, &self.re, &RegexpExtractConfig::new(group_index))?;
+
+        Ok(Arc::new(array))
+    }
+}
+
+/// Extracts a capture group from string views into string views.
+///
+/// Same as [`regexp_extract_view`], but the output is a `StringViewArray` sharing the data
+/// buffers of the input: extracted groups are views into the original strings, so nothing
+/// is copied besides the views themselves.
+///
+/// # Returns
+/// * `Result<ArrayRef>` - Arrow `StringViewArray` containing extracted strings or nulls
+///
+/// # Example
+/// ```
+/// use flarion_task::regexp_extract_view_to_view;
+/// use datafusion::arrow::array::{Array, StringViewArray};
+///
+/// let input = StringViewArray::from(vec![Some("a considerably long hostname123"), None]);
+/// let result = regexp_extract_view_to_view(&input, r"([a-z ]+)(\d+)", 1).unwrap();
+/// let result_array = result.as_any().downcast_ref::<StringViewArray>().unwrap();
+///
+/// assert_eq!(result_array.value(0), "a considerably long hostname");
+/// assert!(result_array.is_null(1));
+/// ```
+pub fn regexp_extract_view_to_view(
+    input: &StringViewArray,
+    pattern: &str,
+    group_index: usize,
+) -> Result<ArrayRef> {
+    let re = compile_extract_regex("regexp_extract", pattern, "")?;
+
+    Ok(Arc::new(extract_view_array(
+        input,
+        &re,
+        &RegexpExtractConfig::new(group_index),
+    )?))
+}
+
+/// [`extract_with_regex`] building views into the data buffers of `input`.
+///
+/// Values borrowed from a string stored out of line point at the same buffer, the others
+/// (short, transformed or `no_match` values) are appended as owned values.
+fn extract_view_array(
+    input: &StringViewArray,
+    re: &ExtractRegex,
+    config: &RegexpExtractConfig,
+) -> Result<StringViewArray> {
+    let mut builder = StringViewBuilder::with_capacity(input.len());
+    // The builder has no blocks yet, so each buffer keeps its index in the output
+    for buffer in input.data_buffers() {
+        builder.append_block(buffer.clone());
+    }
+
+    let group_in_range = group_in_range(re, config.group_index);
+    for (view, optional_data) in input.views().iter().zip(input.iter()) {
+        let Some(data) = optional_data else {
+            builder.append_null();
+            continue;
+        };
+        match extract_value(re, data, config, group_in_range)? {
+            Some(Cow::Borrowed(value))
+                if value.len() > MAX_INLINE_VIEW_LEN
+                    && data.as_bytes().as_ptr_range().contains(&value.as_ptr()) =>
+            {
+                // Strings longer than the inline limit store their buffer index and
+                // offset in the upper 64 bits of the view
+                let (block, offset) = ((*view >> 64) as u32, (*view >> 96) as u32);
+                let start = value.as_ptr() as usize - data.as_ptr() as usize;
+                builder.try_append_view(block, offset + start as u32, value.len() as u32)?;
+            }
+            Some(value) => builder.append_value(value),
+            None => builder.append_null(),
+        }
+    }
+
+    Ok(builder.finish())
+}
+
+/// Strings up to this many bytes are stored inline in their view.
+const MAX_INLINE_VIEW_LEN: usize = 12;
+
+/// DataFusion implementation of [`regexp_extract`] as a [`ScalarUDFImpl`].
+///
+/// Accepts `Utf8`, `LargeUtf8`, `Utf8View`, `List(Utf8)`, `RunEndEncoded(Int32, Utf8)` and
+/// `Dictionary(Int32, _)` of any string type as input, as well as `Int64` and `Float64`
+/// matched as strings like [`regexp_extract_numeric`]. The return type mirrors the
+/// input: `LargeUtf8` for `LargeUtf8`, a dictionary with the same keys for dictionary
+/// input (dictionary columns are passed through by the SQL planner as well),
+/// `List(Utf8)` extracted element-wise for lists, the same run ends for run-end encoded
+/// input and `Utf8` otherwise. The compiled pattern is reused across batches, and a
+/// constant group index is checked against a constant pattern while planning. The group
+/// index can also be a column, applied per row to a constant pattern.
+///
+/// An out-of-range group index is therefore handled differently depending on how it is
+/// given: a literal index with a literal pattern fails the query while planning, even
+/// without [`RegexpExtractUdfBuilder::strict`], while an index or pattern taken from a
+/// column yields an empty string for the rows it is out of range for.
+///
+/// With [`RegexpExtract::with_view_output`] `Utf8View` input yields `Utf8View` output
+/// instead, see [`regexp_extract_view_to_view`]. [`RegexpExtract::with_config`] fixes the
+/// extraction options at construction, and [`RegexpExtractUdfBuilder`] also registers it
+/// under another name or with strict group indices. With
+/// [`RegexpExtract::with_dictionary_output`] plain string input yields a dictionary.
+#[derive(Debug)]
+pub struct RegexpExtract {
+    name: String,
+    signature: Signature,
+    cache: RegexCache,
+    capacity_hint: CapacityHint,
+    view_output: bool,
+    dictionary_output: bool,
+    config: RegexpExtractConfig,
+    strict: bool,
+}
+
+/// Signature of the `regexp_extract` UDF, the argument types it accepts and its volatility.
+///
+/// Every UDF built by [`create_regexp_extract`], [`create_regexp_extract_with`] and
+/// [`RegexpExtractUdfBuilder`] uses it, so a function catalog can be checked against it
+/// without creating a [`ScalarUDF`].
+///
+/// # Example
+/// ```
+/// use flarion_task::{create_regexp_extract, regexp_extract_signature};
+///
+/// assert_eq!(&regexp_extract_signature(), create_regexp_extract().signature());
+/// ```
+pub fn regexp_extract_signature() -> Signature {
+    let mut type_signatures = vec![];
+    for input_type in [
+        DataType::Utf8,
+        DataType::LargeUtf8,
+        DataType::Utf8View,
+        DataType::new_list(DataType::Utf8, true),
+        DataType::RunEndEncoded(
+            Arc::new(Field::new("run_ends", DataType::Int32, false)),
+            Arc::new(Field::new("values", DataType::Utf8, true)),
+        ),
+        // Listed so that the planner passes dictionary columns through instead of casting
+        // them to plain strings, the output is then a dictionary with the same keys
+        DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8)),
+        DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::LargeUtf8)),
+        DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8View)),
+        // Numbers, like the integer and float SQL literals, are formatted as strings
+        DataType::Int64,
+        DataType::Float64,
+        // Only accepted to reject them while planning with a hint to the binary variant
+        DataType::Binary,
+        DataType::LargeBinary,
+    ] {
+        for pattern_type in [DataType::Utf8, DataType::LargeUtf8, DataType::Utf8View] {
+            // Without a group index the whole match is extracted
+            type_signatures.push(TypeSignature::Exact(vec![
+                input_type.clone(),   // StringArray of any kind, ListArray or RunArray of strings
+                pattern_type.clone(), // String of any kind (Pattern)
+            ]));
+            for group_index_type in GROUP_INDEX_TYPES {
+                type_signatures.push(TypeSignature::Exact(vec![
+                    input_type.clone(),   // StringArray of any kind, ListArray or RunArray of strings
+                    pattern_type.clone(), // String of any kind (Pattern)
+                    group_index_type.clone(), // Integer (group_index)
+                ]));
+                type_signatures.push(TypeSignature::Exact(vec![
+                    input_type.clone(),   // StringArray of any kind, ListArray or RunArray of strings
+                    pattern_type.clone(), // String of any kind (Pattern)
+                    group_index_type,     // Integer (group_index)
+                    DataType::Utf8,       // String (flags, as accepted by regexp_extract_flags)
+                ]));
+            }
+        }
+    }
+    Signature::one_of(type_signatures, Volatility::Immutable)
+}
+
+impl RegexpExtract {
+    /// Creates the UDF implementation with an empty pattern cache.
+    pub fn new() -> Self {
+        let signature = regexp_extract_signature();
+
+        Self {
+            name: "regexp_extract".to_string(),
+            signature,
+            cache: RegexCache::default(),
+            capacity_hint: CapacityHint::default(),
+            view_output: false,
+            dictionary_output: false,
+            config: RegexpExtractConfig::default(),
+            strict: false,
+        }
+    }
+
+    /// Returns `Utf8View` for `Utf8View` input, referencing the input buffers instead of
+    /// copying the extracted strings.
+    pub fn with_view_output(mut self, view_output: bool) -> Self {
+        self.view_output = view_output;
+        self
+    }
+
+    /// Extracts as described by `config`, whose `group_index` is used when the group index
+    /// argument is omitted.
+    pub fn with_config(mut self, config: RegexpExtractConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Returns `Dictionary(Int32, Utf8)` for `Utf8`, `LargeUtf8` and `Utf8View` input,
+    /// encoding the extracted values, which are often few distinct ones such as country
+    /// codes, for joins and grouping. Takes precedence over
+    /// [`RegexpExtract::with_view_output`].
+    ///
+    /// Null rows get a null key, while an empty capture or a row the pattern does not
+    /// match gets a key to an empty string value, as in the plain string output.
+    pub fn with_dictionary_output(mut self, dictionary_output: bool) -> Self {
+        self.dictionary_output = dictionary_output;
+        self
+    }
+
+    /// Whether the output for `input_type` is built as string views.
+    fn returns_view(&self, input_type: &DataType) -> bool {
+        self.view_output
+            && input_type == &DataType::Utf8View
+            && !self.returns_dictionary(input_type)
+    }
+
+    /// Whether the output for `input_type` is dictionary encoded after extraction.
+    fn returns_dictionary(&self, input_type: &DataType) -> bool {
+        self.dictionary_output
+            && matches!(
+                input_type,
+                DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View
+            )
+    }
+
+    /// Output type the result of [`RegexpExtract::extract`] is converted to, if any.
+    fn output_type(&self, input_type: &DataType) -> Option<DataType> {
+        if self.returns_dictionary(input_type) {
+            Some(DataType::Dictionary(
+                Box::new(DataType::Int32),
+                Box::new(DataType::Utf8),
+            ))
+        } else if self.returns_view(input_type) {
+            Some(DataType::Utf8View)
+        } else {
+            None
+        }
+    }
+
+    /// Extraction options for `group_index`.
+    fn config(&self, group_index: usize) -> RegexpExtractConfig {
+        RegexpExtractConfig {
+            group_index,
+            ..self.config.clone()
+        }
+    }
+
+    /// [`extract_string_array`] for a `Utf8` batch, with the output builder sized by the
+    /// [`CapacityHint`] learned from the previous batches.
+    fn extract_utf8(
+        &self,
+        input: &StringArray,
+        re: &ExtractRegex,
+        config: &RegexpExtractConfig,
+    ) -> Result<StringArray> {
+        #[cfg(feature = "rayon")]
+        if input.len() >= PARALLEL_THRESHOLD {
+            return extract_parallel(input, re, config);
+        }
+
+        let data_capacity = self.capacity_hint.data_capacity(input);
+        let result = extract_with_capacity(input, re, config, data_capacity)?;
+        self.capacity_hint.record(&result);
+        Ok(result)
+    }
+}
+
+impl Default for RegexpExtract {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ScalarUDFImpl for RegexpExtract {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        if let DataType::Binary | DataType::LargeBinary = &arg_types[0] {
+            return Err(binary_input_error(&arg_types[0]).context("Error during planning"));
+        }
+        Ok(self
+            .output_type(&arg_types[0])
+            .unwrap_or_else(|| extract_return_type(&arg_types[0])))
+    }
+
+    fn return_type_from_exprs(
+        &self,
+        args: &[Expr],
+        _schema: &dyn ExprSchema,
+        arg_types: &[DataType],
+    ) -> Result<DataType> {
+        // With a constant pattern and group index an out-of-range index is reported while
+        // planning, instead of silently producing empty strings once the query runs
+        let flags = match args.get(3) {
+            None => Some(""),
+            Some(Expr::Literal(flags)) => pattern_from_scalar(self.name(), flags).ok().flatten(),
+            Some(_) => None,
+        };
+        if let (Some(Expr::Literal(pattern)), Some(Expr::Literal(group_index)), Some(flags)) =
+            (args.get(1), args.get(2), flags)
+        {
+            if let (Ok(Some(pattern)), false) = (
+                pattern_from_scalar(self.name(), pattern),
+                group_index.is_null(),
+            ) {
+                let planning = |e: DataFusionError| e.context("Error during planning");
+                check_flags(self.name(), flags).map_err(planning)?;
+                let re = self
+                    .cache
+                    .get(self.name(), &self.config.pattern(pattern), flags)?;
+                check_group_index(&re, group_index_from_scalar(self.name(), group_index)?)
+                    .map_err(planning)?;
+            }
+        }
+
+        self.return_type(arg_types)
+    }
+
+    fn invoke_batch(&self, args: &[ColumnarValue], number_rows: usize) -> Result<ColumnarValue> {
+        check_arity_range(self.name(), args, 2, 4)?;
+        let result = self.extract(args, number_rows)?;
+        let Some(output_type) = self.output_type(&args[0].data_type()) else {
+            return Ok(result);
+        };
+
+        // Only a constant pattern on an array builds views directly, the other paths
+        // produce a StringArray that is converted
+        Ok(match result {
+            ColumnarValue::Array(arr) if arr.data_type() != &output_type => {
+                ColumnarValue::Array(cast(&arr, &output_type)?)
+            }
+            ColumnarValue::Scalar(scalar) => ColumnarValue::Scalar(scalar.cast_to(&output_type)?),
+            result => result,
+        })
+    }
+}
+
+impl RegexpExtract {
+    /// [`ScalarUDFImpl::invoke_batch`] before the conversion to view or dictionary output.
+    fn extract(&self, args: &[ColumnarValue], number_rows: usize) -> Result<ColumnarValue> {
+        // A scalar input is processed as a single row array and returned as a scalar, so
+        // that constant expressions can be folded by the optimizer, unless the pattern or
+        // the group index is a column, in which case the input is repeated for every row
+        let per_row_args = args[1..]
+            .iter()
+            .any(|arg| matches!(arg, ColumnarValue::Array(_)));
+        let (input, scalar_input) = match &args[0] {
+            ColumnarValue::Array(arr) => (Arc::clone(arr), false),
+            ColumnarValue::Scalar(scalar) if per_row_args => {
+                (scalar.to_array_of_size(number_rows)?, false)
+            }
+            ColumnarValue::Scalar(scalar) => (scalar.to_array()?, true),
+        };
+
+        let pattern = match &args[1] {
+            ColumnarValue::Scalar(scalar) => pattern_from_scalar(self.name(), scalar)?,
+            ColumnarValue::Array(_) => None,
+        };
+        let flags = match args.get(3) {
+            None => Some(""),
+            Some(ColumnarValue::Scalar(scalar)) => pattern_from_scalar(self.name(), scalar)?,
+            Some(ColumnarValue::Array(flags)) => {
+                return Err(unsupported_input(
+                    self.name(),
+                    "constant flags",
+                    flags.data_type(),
+                ))
+            }
+        };
+        if let Some(flags) = flags {
+            check_flags(self.name(), flags)?;
+        }
+
+        // Like SQL functions in general, a null pattern or null flags make every row null
+        let null_pattern = matches!((&args[1], pattern), (ColumnarValue::Scalar(_), None));
+        let Some(flags) = flags.filter(|_| !null_pattern) else {
+            let return_type = extract_return_type(input.data_type());
+            if scalar_input {
+                return Ok(ColumnarValue::Scalar(ScalarValue::try_from(&return_type)?));
+            }
+            return Ok(ColumnarValue::Array(new_null_array(
+                &return_type,
+                input.len(),
+            )));
+        };
+
+        let group_index = match args.get(2) {
+            Some(ColumnarValue::Scalar(scalar)) => group_index_from_scalar(self.name(), scalar)?,
+            None => self.config.group_index,
+            // A group index column applies to a constant pattern, compiled only once
+            Some(ColumnarValue::Array(group_indices)) => {
+                let Some(pattern) = pattern else {
+                    return Err(unsupported_input(
+                        self.name(),
+                        "a constant pattern with a group index column",
+                        &args[1].data_type(),
+                    ));
+                };
+                let re = self
+                    .cache
+                    .get(self.name(), &self.config.pattern(pattern), flags)?;
+                let group_indices = cast(group_indices, &DataType::Int64)?;
+                let group_indices = group_indices.as_primitive::<Int64Type>();
+                // Negative indices are reported per row
+                if let (true, Some(Ok(max_index))) =
+                    (self.strict, max(group_indices).map(usize::try_from))
+                {
+                    check_group_index(&re, max_index)?;
+                }
+                let result = extract_array_with_group_indices(
+                    input.as_ref(),
+                    &re,
+                    group_indices,
+                    &self.config,
+                )?;
+                return Ok(ColumnarValue::Array(result));
+            }
+        };
+
+        let result = match (&args[1], pattern) {
+            // The pattern is usually a constant, so it is compiled on the first batch only
+            (_, Some(pattern)) => {
+                let re = self
+                    .cache
+                    .get(self.name(), &self.config.pattern(pattern), flags)?;
+                if self.strict {
+                    check_group_index(&re, group_index)?;
+                }
+                let config = self.config(group_index);
+                if self.returns_view(input.data_type()) && !scalar_input {
+                    Arc::new(extract_view_array(input.as_string_view(), &re, &config)?)
+                } else if input.data_type() == &DataType::Utf8 && !scalar_input {
+                    Arc::new(self.extract_utf8(input.as_string(), &re, &config)?)
+                } else {
+                    extract_array(input.as_ref(), &re, &config)?
+                }
+            }
+            // A pattern column is compiled once per distinct pattern within the batch
+            (ColumnarValue::Array(patterns), None) => {
+                let patterns = cast(patterns, &DataType::Utf8)?;
+                if self.strict {
+                    let distinct: HashSet<&str> =
+                        patterns.as_string::<i32>().iter().flatten().collect();
+                    for pattern in distinct {
+                        let pattern = self.config.pattern(pattern);
+                        let re = compile_extract_regex(self.name(), &pattern, flags)?;
+                        check_group_index(&re, group_index)?;
+                    }
+                }
+                let config = self.config(group_index);
+                extract_array_with_patterns(input.as_ref(), patterns.as_string(), flags, &config)?
+            }
+            (ColumnarValue::Scalar(_), None) => unreachable!("null patterns are handled above"),
+        };
+
+        if scalar_input {
+            return Ok(ColumnarValue::Scalar(ScalarValue::try_from_array(
+                &result, 0,
+            )?));
+        }
+        Ok(ColumnarValue::Array(result))
+    }
+}
+
+/// Integer types accepted for the `group_index` argument, so that the planner does not
+/// need an explicit cast for integer literals (which SQL types as `Int64`).
+const GROUP_INDEX_TYPES: [DataType; 8] = [
+    DataType::Int8,
+    DataType::Int16,
+    DataType::Int32,
+    DataType::Int64,
+    DataType::UInt8,
+    DataType::UInt16,
+    DataType::UInt32,
+    DataType::UInt64,
+];
+
+/// Pattern held by a string scalar of any kind, `None` for a null pattern.
+fn pattern_from_scalar<'a>(udf_name: &str, value: &'a ScalarValue) -> Result<Option<&'a str>> {
+    match value {
+        ScalarValue::Utf8(pattern)
+        | ScalarValue::LargeUtf8(pattern)
+        | ScalarValue::Utf8View(pattern) => Ok(pattern.as_deref()),
+        other => Err(unsupported_input(
+            udf_name,
+            "a string pattern",
+            &other.data_type(),
+        )),
+    }
+}
+
+/// Converts an integer `group_index` scalar to `usize`, rejecting negative values.
+fn group_index_from_scalar(udf_name: &str, value: &ScalarValue) -> Result<usize> {
+    let group_index = match value {
+        // Every type but UInt64 fits in i64, so negatives are rejected in one place
+        ScalarValue::Int8(Some(i)) => *i as i64,
+        ScalarValue::Int16(Some(i)) => *i as i64,
+        ScalarValue::Int32(Some(i)) => *i as i64,
+        ScalarValue::Int64(Some(i)) => *i,
+        ScalarValue::UInt8(Some(i)) => *i as i64,
+        ScalarValue::UInt16(Some(i)) => *i as i64,
+        ScalarValue::UInt32(Some(i)) => *i as i64,
+        ScalarValue::UInt64(Some(i)) => {
+            return usize::try_from(*i)
+                .map_err(|_| out_of_range(udf_name, "group_index", i, DataType::UInt64, "usize"))
+        }
+        other => {
+            return Err(unsupported_input(
+                udf_name,
+                "an integer group index",
+                &other.data_type(),
+            ))
+        }
+    };
+
+    usize::try_from(group_index).map_err(|_| negative_group_index(group_index))
+}
+
+/// Error for a negative group index, invalid for any pattern.
+fn negative_group_index(group_index: i64) -> DataFusionError {
+    RegexpError::InvalidGroupIndex {
+        group_index,
+        group_count: None,
+        pattern: None,
+    }
+    .into()
+}
+
+/// Output type of [`extract_array`] for a given input type.
+fn extract_return_type(input_type: &DataType) -> DataType {
+    match input_type {
+        DataType::LargeUtf8 => DataType::LargeUtf8,
+        DataType::Dictionary(key_type, value_type) => {
+            DataType::Dictionary(key_type.clone(), Box::new(extract_return_type(value_type)))
+        }
+        DataType::RunEndEncoded(run_ends, values) => DataType::RunEndEncoded(
+            Arc::clone(run_ends),
+            Arc::new(
+                values
+                    .as_ref()
+                    .clone()
+                    .with_data_type(extract_return_type(values.data_type())),
+            ),
+        ),
+        DataType::List(field) => DataType::List(Arc::new(
+            field
+                .as_ref()
+                .clone()
+                .with_data_type(extract_return_type(field.data_type())),
+        )),
+        _ => DataType::Utf8,
+    }
+}
+
+/// Creates a DataFusion UDF that extracts a capture group from strings using a regular expression pattern.
+///
+/// # Returns
+/// * `ScalarUDF` - User Defined Function that accepts:
+///   - input: string array to process, or a list of strings extracted element-wise, or
+///     an Int64 or Float64 array matched against the formatted numbers
+///   - pattern: regex pattern string (as Utf8, LargeUtf8 or Utf8View), either a constant
+///     or a per-row column (a null pattern yields a null row, a null constant all null rows)
+///   - group_index: optional capture group index (as any signed or unsigned integer type),
+///     the whole match (0) when omitted. With a constant pattern it can also be a per-row
+///     column, a null index yields a null row. An out-of-range literal index with a literal
+///     pattern is a planning error, one from a column yields an empty string.
+///   - flags: optional constant Utf8 flags after the group index, the same as accepted by
+///     [`regexp_extract_flags`], such as `'i'` for case-insensitive matching
+///
+/// Also resolvable as `REGEXP_EXTRACT`, for queries that quote the uppercase Spark name
+/// or run with identifier normalization disabled.
+pub fn create_regexp_extract() -> ScalarUDF {
+    create_regexp_extract_with(RegexpExtractConfig::default())
+}
+
+/// Same as [`create_regexp_extract`], but extracting as described by `config`.
+///
+/// The options are fixed when the UDF is created, `config.group_index` is used when the
+/// group index argument is omitted.
+///
+/// # Example
+/// ```
+/// use flarion_task::{create_regexp_extract_with, NoMatchBehavior, RegexpExtractConfig};
+/// use datafusion::prelude::SessionContext;
+///
+/// let config = RegexpExtractConfig {
+///     no_match: NoMatchBehavior::Null,
+///     ..RegexpExtractConfig::new(1)
+/// };
+/// let ctx = SessionContext::new();
+/// ctx.register_udf(create_regexp_extract_with(config));
+/// ```
+pub fn create_regexp_extract_with(config: RegexpExtractConfig) -> ScalarUDF {
+    ScalarUDF::new_from_impl(RegexpExtract::new().with_config(config))
+        .with_aliases(["REGEXP_EXTRACT"])
+}
+
+/// Builds a [`RegexpExtract`] UDF configured differently from [`create_regexp_extract`].
+///
+/// Each built UDF has its own name, so differently configured versions can be registered
+/// side by side. Unlike [`create_regexp_extract`] no `REGEXP_EXTRACT` alias is added.
+///
+/// # Example
+/// ```
+/// use flarion_task::{NoMatchBehavior, RegexpExtractUdfBuilder};
+/// use datafusion::execution::FunctionRegistry;
+/// use datafusion::prelude::SessionContext;
+///
+/// let ctx = SessionContext::new();
+/// ctx.register_udf(
+///     RegexpExtractUdfBuilder::new()
+///         .name("extract_or_null")
+///         .no_match(NoMatchBehavior::Null)
+///         .build(),
+/// );
+/// ctx.register_udf(RegexpExtractUdfBuilder::new().name("extract_strict").strict(true).build());
+///
+/// assert!(ctx.udf("extract_or_null").is_ok());
+/// assert!(ctx.udf("extract_strict").is_ok());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RegexpExtractUdfBuilder {
+    name: Option<String>,
+    config: RegexpExtractConfig,
+    strict: bool,
+}
+
+impl RegexpExtractUdfBuilder {
+    /// Starts from the defaults of [`create_regexp_extract`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Name the UDF is registered and called by, `regexp_extract` by default.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Output for rows where the pattern does not match, an empty string by default.
+    pub fn no_match(mut self, no_match: NoMatchBehavior) -> Self {
+        self.config.no_match = no_match;
+        self
+    }
+
+    /// Every extraction option at once, see [`RegexpExtract::with_config`].
+    pub fn config(mut self, config: RegexpExtractConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Rejects a group index the pattern does not have, like [`regexp_extract_strict`],
+    /// instead of extracting empty strings for it.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Creates the configured UDF.
+    pub fn build(self) -> ScalarUDF {
+        let mut udf = RegexpExtract::new().with_config(self.config);
+        if let Some(name) = self.name {
+            udf.name = name;
+        }
+        udf.strict = self.strict;
+        ScalarUDF::new_from_impl(udf)
+    }
+}
+
+/// Extracts a capture group from binary values using a regular expression pattern.
+///
+/// Same as [`regexp_extract`] but matches raw bytes with [`regex::bytes::Regex`], so values
+/// that are not valid UTF-8 are handled without a lossy conversion. Unicode mode follows
+/// [`RegexpOptions::unicode`], disable it there or with `(?-u)` to match arbitrary bytes
+/// with `.` or `\xFF`.
+///
+/// # Returns
+/// * `Result<ArrayRef>` - Arrow `BinaryArray` containing extracted bytes or nulls
+///
+/// # Example
+/// ```
+/// use flarion_task::regexp_extract_binary;
+/// use datafusion::arrow::array::{Array, BinaryArray};
+///
+/// let input = BinaryArray::from(vec![Some(&b"id=42 \xFF\xFE"[..]), None]);
+/// let result = regexp_extract_binary(&input, r"id=(\d+)", 1).unwrap();
+/// let result_array = result.as_any().downcast_ref::<BinaryArray>().unwrap();
+///
+/// assert_eq!(result_array.value(0), b"42");
+/// assert!(result_array.is_null(1));
+/// ```
+pub fn regexp_extract_binary(
+    input: &BinaryArray,
+    pattern: &str,
+    group_index: usize,
+) -> Result<ArrayRef> {
+    let re = compile_bytes_regex("regexp_extract_binary", pattern)?;
+    let array = extract_bytes(
+        input.iter(),
+        input.len(),
+        input.values().len(),
+        &re,
+        group_index,
+    );
+
+    Ok(Arc::new(array))
+}
+
+/// Extracts a capture group from fixed-width binary values, such as fixed-width codes.
+///
+/// Same as [`regexp_extract_binary`], every value is matched as a byte string of exactly
+/// the width of the type. Padding bytes are part of the value and can be matched by the
+/// pattern, so a code padded with spaces or `\0` can be anchored with `$` only if the
+/// pattern accounts for the padding, e.g. `(\w+) *$`.
+///
+/// # Returns
+/// * `Result<ArrayRef>` - Arrow `BinaryArray` containing extracted bytes or nulls
+///
+/// # Example
+/// ```
+/// use flarion_task::regexp_extract_fixed_size_binary;
+/// use datafusion::arrow::array::{Array, AsArray, FixedSizeBinaryArray};
+///
+/// let input = FixedSizeBinaryArray::try_from_iter([b"US-00042\0\0\0\0"].into_iter()).unwrap();
+/// let result = regexp_extract_fixed_size_binary(&input, r"^([A-Z]{2})-(\d+)", 2).unwrap();
+///
+/// assert_eq!(result.as_binary::<i32>().value(0), b"00042");
+/// ```
+pub fn regexp_extract_fixed_size_binary(
+    input: &FixedSizeBinaryArray,
+    pattern: &str,
+    group_index: usize,
+) -> Result<ArrayRef> {
+    let re = compile_bytes_regex("regexp_extract_fixed_size_binary", pattern)?;
+    let data_len = input.len() * input.value_length() as usize;
+    let array = extract_bytes(input.iter(), input.len(), data_len, &re, group_index);
+
+    Ok(Arc::new(array))
+}
+
+/// Compiles a pattern for matching raw bytes, with the same options and caching as
+/// [`compile_regex`].
+fn compile_bytes_regex(udf_name: &str, pattern: &str) -> Result<Arc<regex::bytes::Regex>> {
+    cached_regex(&GLOBAL_BYTES_REGEX_CACHE, pattern, "", || {
+        let options = regexp_options();
+        regex::bytes::RegexBuilder::new(pattern)
+            .size_limit(options.size_limit)
+            .dfa_size_limit(options.dfa_size_limit)
+            .unicode(options.unicode)
+            .build()
+            .map_err(|e| invalid_pattern(udf_name, pattern, e))
+    })
+}
+
+/// Extracts `group_index` from every byte string of `input`, with `len` rows of
+/// `data_len` bytes in total.
+fn extract_bytes<'a>(
+    input: impl IntoIterator<Item = Option<&'a [u8]>>,
+    len: usize,
+    data_len: usize,
+    re: &regex::bytes::Regex,
+    group_index: usize,
+) -> BinaryArray {
+    // Extracted values are slices of the input, so its data size is an upper bound
+    let mut builder = BinaryBuilder::with_capacity(len, data_len);
+    for optional_data in input {
+        match optional_data {
+            Some(data) => builder.append_value(
+                re.captures(data)
+                    .and_then(|captures| captures.get(group_index))
+                    .map_or(&[][..], |m| m.as_bytes()),
+            ),
+            None => builder.append_null(),
+        }
+    }
+
+    builder.finish()
+}
+
+/// Creates a DataFusion UDF that extracts a capture group from binary values using a regular expression pattern.
+///
+/// # Returns
+/// * `ScalarUDF` - User Defined Function that accepts:
+///   - input: binary array to process
+///   - pattern: regex pattern string
+///   - group_index: capture group index (as any integer type, like `regexp_extract`).
+pub fn create_regexp_extract_binary() -> ScalarUDF {
+    let type_signatures = GROUP_INDEX_TYPES
+        .into_iter()
+        .map(|group_index_type| {
+            TypeSignature::Exact(vec![
+                DataType::Binary, // BinaryArray (input)
+                DataType::Utf8,   // String (pattern)
+                group_index_type, // Integer (group_index)
+            ])
+        })
+        .collect();
+
+    let fun: ScalarFunctionImplementation =
+        Arc::new(|args: &[ColumnarValue]| -> Result<ColumnarValue> {
+            check_arity("regexp_extract_binary", args, 3)?;
+            let unsupported = || {
+                unsupported_input(
+                    "regexp_extract_binary",
+                    "a Binary column",
+                    &args[0].data_type(),
+                )
+            };
+            let input = match &args[0] {
+                ColumnarValue::Array(arr) => arr
+                    .as_any()
+                    .downcast_ref::<BinaryArray>()
+                    .ok_or_else(unsupported)?,
+                _ => return Err(unsupported()),
+            };
+
+            let pattern = string_arg("regexp_extract_binary", args.get(1), "pattern")?;
+            let group_index = match args.get(2) {
+                Some(ColumnarValue::Scalar(scalar)) if !scalar.is_null() => {
+                    group_index_from_scalar("regexp_extract_binary", scalar)?
+                }
+                arg => {
+                    return Err(constant_arg_error(
+                        "regexp_extract_binary",
+                        arg,
+                        "a non-null integer constant as group index",
+                    ))
+                }
+            };
+
+            Ok(ColumnarValue::Array(regexp_extract_binary(
+                input,
+                pattern,
+                group_index,
+            )?))
+        });
+
+    // Mark as immutable (does not depend on the data)
+    ScalarUDF::from(SimpleScalarUDF::new_with_signature(
+        "regexp_extract_binary",
+        Signature::one_of(type_signatures, Volatility::Immutable),
+        DataType::Binary,
+        fun,
+    ))
+}
+
+/// Replaces every match of a regular expression pattern in strings with a replacement.
+///
+/// # Arguments
+/// * `input` - Input string array to process
+/// * `pattern` - Regular expression pattern to match
+/// * `replacement` - Replacement string, may reference capture groups as `$1` or `${name}`
+///
+/// # Returns
+/// * `Result<ArrayRef>` - Arrow array containing replaced strings or nulls
+///
+/// # Example
+/// ```
+/// use flarion_task::regexp_replace;
+/// use datafusion::arrow::array::{Array, StringArray};
+///
+/// let input = StringArray::from(vec![Some("abc123"), None]);
+/// let result = regexp_replace(&input, r"(\d+)", "#$1").unwrap();
+/// let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+///
+/// assert_eq!(result_array.value(0), "abc#123");
+/// assert!(result_array.is_null(1));
+/// ```
+pub fn regexp_replace(input: &StringArray, pattern: &str, replacement: &str) -> Result<ArrayRef> {
+    let re = compile_regex("regexp_replace", pattern)?;
+
+    let array: StringArray = input
+        .iter()
+        .map(|optional_data| optional_data.map(|data| re.replace_all(data, replacement)))
+        .collect();
+
+    Ok(Arc::new(array))
+}
+
+/// Creates a DataFusion UDF that replaces every match of a regular expression pattern in strings.
+///
+/// # Returns
+/// * `ScalarUDF` - User Defined Function that accepts:
+///   - input: string array to process
+///   - pattern: regex pattern string
+///   - replacement: replacement string (supports `$1` / `${name}` backreferences).
+pub fn create_regexp_replace() -> ScalarUDF {
+    regex_udf!(
+        "regexp_replace" -> DataType::Utf8,
+        |input, pattern, replacement: Utf8| regexp_replace(input, pattern, replacement)
+    )
+}
+
+/// Replaces every match of a regular expression pattern in strings with the result of a
+/// closure.
+///
+/// Like [`regex::Regex::replace_all`] with a function, for replacements too complex for
+/// `$1` templates. This is a library function only, there is no SQL UDF for it.
+///
+/// # Arguments
+/// * `input` - Input string array to process
+/// * `pattern` - Regular expression pattern to match
+/// * `f` - Computes the replacement of a match from its capture groups
+///
+/// # Returns
+/// * `Result<ArrayRef>` - Arrow array containing replaced strings or nulls
+///
+/// # Example
+/// ```
+/// use flarion_task::regexp_replace_with;
+/// use datafusion::arrow::array::{Array, StringArray};
+///
+/// let input = StringArray::from(vec![Some("1 + 2"), None]);
+/// let result = regexp_replace_with(&input, r"\d+", |caps| {
+///     (caps[0].parse::<i32>().unwrap() * 10).to_string()
+/// })
+/// .unwrap();
+/// let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+///
+/// assert_eq!(result_array.value(0), "10 + 20");
+/// assert!(result_array.is_null(1));
+/// ```
+pub fn regexp_replace_with<F>(input: &StringArray, pattern: &str, f: F) -> Result<ArrayRef>
+where
+    F: Fn(&Captures) -> String,
+{
+    let re = compile_regex("regexp_replace_with", pattern)?;
+
+    let array: StringArray = input
+        .iter()
+        .map(|optional_data| optional_data.map(|data| re.replace_all(data, &f)))
+        .collect();
+
+    Ok(Arc::new(array))
+}
+
+/// Masks every match of a regular expression pattern in strings, replacing each matched
+/// character with `mask_char`.
+///
+/// Unlike [`regexp_replace`] with a constant, the masked strings keep their length in
+/// characters, e.g. to scrub PII without changing the layout of the text.
+///
+/// # Arguments
+/// * `input` - Input string array to process
+/// * `pattern` - Regular expression pattern to match
+/// * `mask_char` - Character written over every character of a match
+///
+/// # Returns
+/// * `Result<ArrayRef>` - Arrow array containing masked strings or nulls
+///
+/// # Example
+/// ```
+/// use flarion_task::regexp_mask;
+/// use datafusion::arrow::array::{Array, StringArray};
+///
+/// let input = StringArray::from(vec![Some("call 555-1234"), None]);
+/// let result = regexp_mask(&input, r"\d", '*').unwrap();
+/// let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+///
+/// assert_eq!(result_array.value(0), "call ***-****");
+/// assert!(result_array.is_null(1));
+/// ```
+pub fn regexp_mask(input: &StringArray, pattern: &str, mask_char: char) -> Result<ArrayRef> {
+    let re = compile_regex("regexp_mask", pattern)?;
+    let mask = |caps: &Captures| {
+        std::iter::repeat_n(mask_char, caps[0].chars().count()).collect::<String>()
+    };
+
+    let array: StringArray = input
+        .iter()
+        .map(|optional_data| optional_data.map(|data| re.replace_all(data, mask)))
+        .collect();
+
+    Ok(Arc::new(array))
+}
+
+/// Replaces the first match of a regular expression pattern in strings with a replacement.
+///
+/// # Arguments
+/// * `input` - Input string array to process
+/// * `pattern` - Regular expression pattern to match
+/// * `replacement` - Replacement string, may reference capture groups as `$1` or `${name}`
+///
+/// # Returns
+/// * `Result<ArrayRef>` - Arrow array containing replaced strings or nulls
+///
+/// # Example
+/// ```
+/// use flarion_task::regexp_replace_first;
+/// use datafusion::arrow::array::{Array, StringArray};
+///
+/// let input = StringArray::from(vec![Some("a1b2c3"), None]);
+/// let result = regexp_replace_first(&input, r"\d+", "X").unwrap();
+/// let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+///
+/// assert_eq!(result_array.value(0), "aXb2c3");
+/// assert!(result_array.is_null(1));
+/// ```
+pub fn regexp_replace_first(
+    input: &StringArray,
+    pattern: &str,
+    replacement: &str,
+) -> Result<ArrayRef> {
+    let re = compile_regex("regexp_replace_first", pattern)?;
+
+    let array: StringArray = input
+        .iter()
+        .map(|optional_data| optional_data.map(|data| re.replace(data, replacement)))
+        .collect();
+
+    Ok(Arc::new(array))
+}
+
+/// Creates a DataFusion UDF that replaces the first match of a regular expression pattern in strings.
+///
+/// # Returns
+/// * `ScalarUDF` - User Defined Function that accepts:
+///   - input: string array to process
+///   - pattern: regex pattern string
+///   - replacement: replacement string (supports `$1` / `${name}` backreferences).
+pub fn create_regexp_replace_first() -> ScalarUDF {
+    regex_udf!(
+        "regexp_replace_first" -> DataType::Utf8,
+        |input, pattern, replacement: Utf8| regexp_replace_first(input, pattern, replacement)
+    )
+}
+
+/// Replaces at most `count` matches of a regular expression pattern in strings with a replacement.
+///
+/// # Arguments
+/// * `input` - Input string array to process
+/// * `pattern` - Regular expression pattern to match
+/// * `replacement` - Replacement string, may reference capture groups as `$1` or `${name}`
+/// * `count` - Maximum number of matches to replace per row, 0 replaces all of them
+///
+/// # Returns
+/// * `Result<ArrayRef>` - Arrow array containing replaced strings or nulls
+///
+/// # Example
+/// ```
+/// use flarion_task::regexp_replace_n;
+/// use datafusion::arrow::array::{Array, StringArray};
+///
+/// let input = StringArray::from(vec![Some("a1b2c3"), None]);
+/// let result = regexp_replace_n(&input, r"\d", "X", 2).unwrap();
+/// let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+///
+/// assert_eq!(result_array.value(0), "aXbXc3");
+/// assert!(result_array.is_null(1));
+/// ```
+pub fn regexp_replace_n(
+    input: &StringArray,
+    pattern: &str,
+    replacement: &str,
+    count: usize,
+) -> Result<ArrayRef> {
+    let re = compile_regex("regexp_replace_n", pattern)?;
+
+    let array: StringArray = input
+        .iter()
+        .map(|optional_data| optional_data.map(|data| re.replacen(data, count, replacement)))
+        .collect();
+
+    Ok(Arc::new(array))
+}
+
+/// Creates a DataFusion UDF that replaces at most a given number of matches of a regular expression pattern in strings.
+///
+/// # Returns
+/// * `ScalarUDF` - User Defined Function that accepts:
+///   - input: string array to process
+///   - pattern: regex pattern string
+///   - replacement: replacement string (supports `$1` / `${name}` backreferences)
+///   - count: maximum number of matches to replace per row, 0 for all (as UInt32).
+pub fn create_regexp_replace_n() -> ScalarUDF {
+    regex_udf!(
+        "regexp_replace_n" -> DataType::Utf8,
+        |input, pattern, replacement: Utf8, count: UInt32|
+            regexp_replace_n(input, pattern, replacement, count)
+    )
+}
+
+/// Extracts a named capture group from strings using a regular expression pattern.
+///
+/// # Arguments
+/// * `input` - Input string array to process
+/// * `pattern` - Regular expression pattern to match
+/// * `group_name` - Name of the capture group to extract, e.g. `year` for `(?P<year>\d{4})`
+///
+/// # Returns
+/// * `Result<ArrayRef>` - Arrow array containing extracted strings or nulls.
+///   Returns an error if the pattern has no group named `group_name`.
+///
+/// # Example
+/// ```
+/// use flarion_task::regexp_extract_named;
+/// use datafusion::arrow::array::{Array, StringArray};
+///
+/// let input = StringArray::from(vec![Some("born 1984-05"), None]);
+/// let result = regexp_extract_named(&input, r"(?P<year>\d{4})-(?P<month>\d{2})", "year").unwrap();
+/// let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+///
+/// assert_eq!(result_array.value(0), "1984");
+/// assert!(result_array.is_null(1));
+/// ```
+pub fn regexp_extract_named(
+    input: &StringArray,
+    pattern: &str,
+    group_name: &str,
+) -> Result<ArrayRef> {
+    let re = compile_regex("regexp_extract_named", pattern)?;
+
+    if !re.capture_names().flatten().any(|name| name == group_name) {
+        return Err(RegexpError::UnknownGroupName {
+            function: "regexp_extract_named".to_string(),
+            group_name: group_name.to_string(),
+            pattern: pattern.to_string(),
+        }
+        .into());
+    }
+
+    // Matched slices are appended by reference, sized from the input they are taken from
+    let mut builder = StringBuilder::with_capacity(input.len(), string_data_len(input));
+    for optional_data in input.iter() {
+        match optional_data {
+            Some(data) => builder.append_value(
+                re.captures(data)
+                    .and_then(|captures| captures.name(group_name))
+                    .map_or("", |m| m.as_str()),
+            ),
+            None => builder.append_null(),
+        }
+    }
+
+    Ok(Arc::new(builder.finish()))
+}
+
+/// Creates a DataFusion UDF that extracts a capture group from strings using a regular expression pattern and flags.
+///
+/// # Returns
+/// * `ScalarUDF` - User Defined Function that accepts:
+///   - input: string array to process
+///   - pattern: regex pattern string
+///   - group_index: capture group index (as UInt32)
+///   - flags: regex flags string (e.g. `"im"`).
+pub fn create_regexp_extract_flags() -> ScalarUDF {
+    regex_udf!(
+        "regexp_extract_flags" -> DataType::Utf8,
+        |input, pattern, group_index: UInt32, flags: Utf8|
+            regexp_extract_flags(input, pattern, group_index, flags)
+    )
+}
+
+/// Creates a DataFusion UDF that extracts a named capture group from strings using a regular expression pattern.
+///
+/// # Returns
+/// * `ScalarUDF` - User Defined Function that accepts:
+///   - input: string array to process
+///   - pattern: regex pattern string
+///   - group_name: capture group name string.
+pub fn create_regexp_extract_named() -> ScalarUDF {
+    regex_udf!(
+        "regexp_extract_named" -> DataType::Utf8,
+        |input, pattern, group_name: Utf8| regexp_extract_named(input, pattern, group_name)
+    )
+}
+
+/// Extracts every capture group of a regular expression pattern at once.
+///
+/// # Arguments
+/// * `input` - Input string array to process
+/// * `pattern` - Regular expression pattern to match
+///
+/// # Returns
+/// * `Result<ArrayRef>` - Arrow `StructArray` with one `Utf8` field per capture group,
+///   named after the group if it is named and `g1`, `g2`, ... otherwise, with underscores
+///   appended to a generated name a named group already has. Groups that did not match
+///   yield empty strings, null inputs yield null rows.
+///
+/// # Example
+/// ```
+/// use flarion_task::regexp_extract_struct;
+/// use datafusion::arrow::array::{Array, StringArray, StructArray};
+///
+/// let input = StringArray::from(vec![Some("hello123"), None]);
+/// let result = regexp_extract_struct(&input, r"(?P<word>[a-z]+)(\d+)").unwrap();
+/// let result_array = result.as_any().downcast_ref::<StructArray>().unwrap();
+///
+/// let word = result_array.column_by_name("word").unwrap();
+/// let word = word.as_any().downcast_ref::<StringArray>().unwrap();
+/// let digits = result_array.column_by_name("g2").unwrap();
+/// let digits = digits.as_any().downcast_ref::<StringArray>().unwrap();
+/// assert_eq!(word.value(0), "hello");
+/// assert_eq!(digits.value(0), "123");
+/// assert!(result_array.is_null(1));
+/// ```
+pub fn regexp_extract_struct(input: &StringArray, pattern: &str) -> Result<ArrayRef> {
+    let re = compile_regex("regexp_extract_struct", pattern)?;
+    let fields = group_fields(&re);
+
+    if fields.is_empty() {
+        return Ok(Arc::new(StructArray::new_empty_fields(
+            input.len(),
+            input.nulls().cloned(),
+        )));
+    }
+
+    let mut builders: Vec<StringBuilder> = fields.iter().map(|_| StringBuilder::new()).collect();
+    for optional_data in input.iter() {
+        let captures = optional_data.map(|data| re.captures(data));
+        for (i, builder) in builders.iter_mut().enumerate() {
+            match &captures {
+                Some(captures) => builder.append_value(
+                    captures
+                        .as_ref()
+                        .and_then(|captures| captures.get(i + 1))
+                        .map_or("", |m| m.as_str()),
+                ),
+                None => builder.append_null(),
+            }
+        }
+    }
+
+    let columns: Vec<ArrayRef> = builders
+        .iter_mut()
+        .map(|builder| Arc::new(builder.finish()) as ArrayRef)
+        .collect();
+
+    Ok(Arc::new(StructArray::try_new(
+        fields,
+        columns,
+        input.nulls().cloned(),
+    )?))
+}
+
+/// Extracts a capture group together with whether the pattern matched, in a single pass.
+///
+/// Unlike [`regexp_extract`], an empty capture can be told apart from a row the pattern
+/// does not match, without running [`regexp_like`] separately.
+///
+/// # Arguments
+/// * `input` - Input string array to process
+/// * `pattern` - Regular expression pattern to match
+/// * `group_index` - Index of the capture group to extract (0 for full match)
+///
+/// # Returns
+/// * `Result<ArrayRef>` - Arrow `StructArray` with a `value` field holding what
+///   [`regexp_extract`] returns and a `matched` field that is true for rows the pattern
+///   matches, even if the group itself did not participate. Null inputs yield null rows.
+///
+/// # Example
+/// ```
+/// use flarion_task::regexp_extract_with_flag;
+/// use datafusion::arrow::array::{Array, AsArray, StringArray};
+///
+/// let input = StringArray::from(vec![Some("hello123"), Some("hello"), None]);
+/// let result = regexp_extract_with_flag(&input, r"([a-z]+)(\d*)", 2).unwrap();
+/// let result_array = result.as_struct();
+///
+/// let value = result_array.column_by_name("value").unwrap().as_string::<i32>();
+/// let matched = result_array.column_by_name("matched").unwrap().as_boolean();
+/// assert_eq!((value.value(0), matched.value(0)), ("123", true));
+/// assert_eq!((value.value(1), matched.value(1)), ("", true));
+/// assert!(result_array.is_null(2));
+/// ```
+pub fn regexp_extract_with_flag(
+    input: &StringArray,
+    pattern: &str,
+    group_index: usize,
+) -> Result<ArrayRef> {
+    let re = compile_extract_regex("regexp_extract_with_flag", pattern, "")?;
+    let config = RegexpExtractConfig::new(group_index);
+    let group_in_range = group_in_range(&re, group_index);
+
+    let mut values = StringBuilder::with_capacity(input.len(), string_data_len(input));
+    let mut matched = BooleanBuilder::with_capacity(input.len());
+    for optional_data in input.iter() {
+        let Some(data) = optional_data else {
+            values.append_null();
+            matched.append_null();
+            continue;
+        };
+        match extract_match(&re, data, &config, group_in_range)? {
+            Some(value) => {
+                values.append_option(value);
+                matched.append_value(true);
+            }
+            None => {
+                values.append_option(no_match_value(&re, &config.no_match)?);
+                matched.append_value(false);
+            }
+        }
+    }
+
+    let fields = Fields::from(vec![
+        Field::new("value", DataType::Utf8, true),
+        Field::new("matched", DataType::Boolean, true),
+    ]);
+    Ok(Arc::new(StructArray::try_new(
+        fields,
+        vec![Arc::new(values.finish()), Arc::new(matched.finish())],
+        input.nulls().cloned(),
+    )?))
+}
+
+/// Splits strings into the first match of a regular expression pattern and what follows it.
+///
+/// Useful for tokenizing: applying it again to `rest` yields the next token.
+///
+/// # Arguments
+/// * `input` - Input string array to process
+/// * `pattern` - Regular expression pattern to match
+///
+/// # Returns
+/// * `Result<ArrayRef>` - Arrow `StructArray` with a `matched` field holding the full
+///   match and a `rest` field holding everything after the end of the match. Rows the
+///   pattern does not match yield an empty `matched` and the whole string as `rest`.
+///   Null inputs yield null rows.
+///
+/// # Example
+/// ```
+/// use flarion_task::regexp_extract_rest;
+/// use datafusion::arrow::array::{AsArray, StringArray};
+///
+/// let input = StringArray::from(vec!["GET /index.html"]);
+/// let result = regexp_extract_rest(&input, r"\w+\s*").unwrap();
+/// let result_array = result.as_struct();
+///
+/// let matched = result_array.column_by_name("matched").unwrap().as_string::<i32>();
+/// let rest = result_array.column_by_name("rest").unwrap().as_string::<i32>();
+/// assert_eq!((matched.value(0), rest.value(0)), ("GET ", "/index.html"));
+/// ```
+pub fn regexp_extract_rest(input: &StringArray, pattern: &str) -> Result<ArrayRef> {
+    let re = compile_regex("regexp_extract_rest", pattern)?;
+
+    let mut matched = StringBuilder::with_capacity(input.len(), string_data_len(input));
+    let mut rest = StringBuilder::with_capacity(input.len(), string_data_len(input));
+    for optional_data in input.iter() {
+        let Some(data) = optional_data else {
+            matched.append_null();
+            rest.append_null();
+            continue;
+        };
+        match re.find(data) {
+            Some(m) => {
+                matched.append_value(m.as_str());
+                rest.append_value(&data[m.end()..]);
+            }
+            None => {
+                matched.append_value("");
+                rest.append_value(data);
+            }
+        }
+    }
+
+    let fields = Fields::from(vec![
+        Field::new("matched", DataType::Utf8, true),
+        Field::new("rest", DataType::Utf8, true),
+    ]);
+    Ok(Arc::new(StructArray::try_new(
+        fields,
+        vec![Arc::new(matched.finish()), Arc::new(rest.finish())],
+        input.nulls().cloned(),
+    )?))
+}
+
+/// Extracts the full match together with a capture group, in a single pass.
+///
+/// # Arguments
+/// * `input` - Input string array to process
+/// * `pattern` - Regular expression pattern to match
+/// * `group_index` - Index of the capture group to extract alongside the full match
+///
+/// # Returns
+/// * `Result<ArrayRef>` - Arrow `StructArray` with a `full` field holding group 0 and a
+///   `group` field holding what [`regexp_extract`] returns for `group_index`. Rows the
+///   pattern does not match yield two empty strings, null inputs yield null rows.
+///
+/// # Example
+/// ```
+/// use flarion_task::regexp_extract_pair;
+/// use datafusion::arrow::array::{AsArray, StringArray};
+///
+/// let input = StringArray::from(vec!["took 250ms"]);
+/// let result = regexp_extract_pair(&input, r"(\d+)ms", 1).unwrap();
+/// let result_array = result.as_struct();
+///
+/// let full = result_array.column_by_name("full").unwrap().as_string::<i32>();
+/// let group = result_array.column_by_name("group").unwrap().as_string::<i32>();
+/// assert_eq!((full.value(0), group.value(0)), ("250ms", "250"));
+/// ```
+pub fn regexp_extract_pair(
+    input: &StringArray,
+    pattern: &str,
+    group_index: usize,
+) -> Result<ArrayRef> {
+    let re = compile_regex("regexp_extract_pair", pattern)?;
+
+    let mut full = StringBuilder::with_capacity(input.len(), string_data_len(input));
+    let mut group = StringBuilder::with_capacity(input.len(), string_data_len(input));
+    for optional_data in input.iter() {
+        let Some(data) = optional_data else {
+            full.append_null();
+            group.append_null();
+            continue;
+        };
+        match re.captures(data) {
+            Some(captures) => {
+                full.append_value(&captures[0]);
+                group.append_value(captures.get(group_index).map_or("", |m| m.as_str()));
+            }
+            None => {
+                full.append_value("");
+                group.append_value("");
+            }
+        }
+    }
+
+    let fields = Fields::from(vec![
+        Field::new("full", DataType::Utf8, true),
+        Field::new("group", DataType::Utf8, true),
+    ]);
+    Ok(Arc::new(StructArray::try_new(
+        fields,
+        vec![Arc::new(full.finish()), Arc::new(group.finish())],
+        input.nulls().cloned(),
+    )?))
+}
+
+/// Extracts a capture group together with the character position it starts at.
+///
+/// # Arguments
+/// * `input` - Input string array to process
+/// * `pattern` - Regular expression pattern to match
+/// * `group_index` - Index of the capture group to extract (0 for full match)
+///
+/// # Returns
+/// * `Result<ArrayRef>` - Arrow `StructArray` with a `value` field holding what
+///   [`regexp_extract`] returns and a `pos` field holding the 0-based character offset of
+///   the group in the string. `pos` is -1 when the pattern does not match or the group
+///   does not participate in the match. Null inputs yield null rows. An offset past
+///   `i32::MAX` characters is an error rather than a wrapped-around position.
+///
+/// # Example
+/// ```
+/// use flarion_task::regexp_extract_with_pos;
+/// use datafusion::arrow::array::{AsArray, StringArray};
+/// use datafusion::arrow::datatypes::Int32Type;
+///
+/// let input = StringArray::from(vec!["size: 42", "none"]);
+/// let result = regexp_extract_with_pos(&input, r"(\d+)", 1).unwrap();
+/// let result_array = result.as_struct();
+///
+/// let value = result_array.column_by_name("value").unwrap().as_string::<i32>();
+/// let pos = result_array.column_by_name("pos").unwrap().as_primitive::<Int32Type>();
+/// assert_eq!((value.value(0), pos.value(0)), ("42", 6));
+/// assert_eq!((value.value(1), pos.value(1)), ("", -1));
+/// ```
+pub fn regexp_extract_with_pos(
+    input: &StringArray,
+    pattern: &str,
+    group_index: usize,
+) -> Result<ArrayRef> {
+    let re = compile_regex("regexp_extract_with_pos", pattern)?;
+
+    let mut values = StringBuilder::with_capacity(input.len(), string_data_len(input));
+    let mut positions = Vec::with_capacity(input.len());
+    for optional_data in input.iter() {
+        let Some(data) = optional_data else {
+            values.append_null();
+            positions.push(None);
+            continue;
+        };
+        match re
+            .captures(data)
+            .and_then(|captures| captures.get(group_index))
+        {
+            Some(m) => {
+                values.append_value(m.as_str());
+                let pos = data[..m.start()].chars().count();
+                positions.push(Some(int32_offset("regexp_extract_with_pos", pos)?));
+            }
+            None => {
+                values.append_value("");
+                positions.push(Some(-1));
+            }
+        }
+    }
+
+    let fields = Fields::from(vec![
+        Field::new("value", DataType::Utf8, true),
+        Field::new("pos", DataType::Int32, true),
+    ]);
+    Ok(Arc::new(StructArray::try_new(
+        fields,
+        vec![
+            Arc::new(values.finish()),
+            Arc::new(Int32Array::from(positions)),
+        ],
+        input.nulls().cloned(),
+    )?))
+}
+
+/// Struct fields holding the capture groups of `re`, named after the group if it is named
+/// and `g1`, `g2`, ... otherwise.
+///
+/// A generated name already taken by a named group, such as `g2` in `(?P<g2>a)(b)`, gets
+/// underscores appended until it is unique, so that every field can be selected by name.
+fn group_fields(re: &Regex) -> Fields {
+    let named: HashSet<&str> = re.capture_names().flatten().collect();
+    // Group 0 is the full match and is not part of the struct
+    re.capture_names()
+        .enumerate()
+        .skip(1)
+        .map(|(i, name)| {
+            let name = name.map_or_else(
+                || {
+                    let mut name = format!("g{i}");
+                    while named.contains(name.as_str()) {
+                        name.push('_');
+                    }
+                    name
+                },
+                |name| name.to_string(),
+            );
+            Field::new(name, DataType::Utf8, true)
+        })
+        .collect()
+}
+
+/// Captures of every match of a regex in a string, see [`all_captures`].
+enum AllCaptures<'r, 'h> {
+    Successive {
+        re: &'r Regex,
+        data: &'h str,
+        // `None` once the search went past the end of `data`
+        start: Option<usize>,
+    },
+    Overlapping {
+        re: &'r Regex,
+        data: &'h str,
+        // `None` once the search went past the end of `data`
+        start: Option<usize>,
+    },
+}
+
+impl<'h> Iterator for AllCaptures<'_, 'h> {
+    type Item = regex::Captures<'h>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            AllCaptures::Successive { re, data, start } => {
+                let captures = re.captures_at(data, (*start)?)?;
+                *start = spark_next_start(data, captures.get(0).unwrap());
+                Some(captures)
+            }
+            AllCaptures::Overlapping { re, data, start } => {
+                let captures = re.captures_at(data, (*start)?)?;
+                let match_start = captures.get(0).unwrap().start();
+                *start = data[match_start..]
+                    .chars()
+                    .next()
+                    .map(|c| match_start + c.len_utf8());
+                Some(captures)
+            }
+        }
+    }
+}
+
+/// Iterates over the captures of every match of `re` in `data`, as [`spark_find_iter`]
+/// does over the matches. With `overlapping` the search resumes one character after the
+/// start of a match instead of after its end.
+fn all_captures<'r, 'h>(re: &'r Regex, data: &'h str, overlapping: bool) -> AllCaptures<'r, 'h> {
+    if overlapping {
+        AllCaptures::Overlapping {
+            re,
+            data,
+            start: Some(0),
+        }
+    } else {
+        AllCaptures::Successive {
+            re,
+            data,
+            start: Some(0),
+        }
+    }
+}
+
+/// Extracts a capture group from every match of a regular expression pattern in strings.
+///
+/// Matches are found like Spark does, and like [`regexp_count`] counts them: after a
+/// zero-width match the search resumes one character further, and a zero-width match
+/// directly following another match is reported, so `(a*)` on `"aaa"` yields
+/// `["aaa", ""]`.
+///
+/// # Arguments
+/// * `input` - Input string array to process
+/// * `pattern` - Regular expression pattern to match
+/// * `group_index` - Index of the capture group to extract (0 for full match)
+///
+/// # Returns
+/// * `Result<ArrayRef>` - Arrow `ListArray` of strings, one list per input row.
+///   Null inputs produce a null list, rows without matches produce an empty list.
+///
+/// # Example
+/// ```
+/// use flarion_task::regexp_extract_all;
+/// use datafusion::arrow::array::{Array, ListArray, StringArray};
+///
+/// let input = StringArray::from(vec![Some("a1b22c333"), None]);
+/// let result = regexp_extract_all(&input, r"(\d+)", 1).unwrap();
+/// let result_array = result.as_any().downcast_ref::<ListArray>().unwrap();
+///
+/// let first = result_array.value(0);
+/// let first = first.as_any().downcast_ref::<StringArray>().unwrap();
+/// assert_eq!(first.iter().flatten().collect::<Vec<_>>(), vec!["1", "22", "333"]);
+/// assert!(result_array.is_null(1));
+/// ```
+pub fn regexp_extract_all(
+    input: &StringArray,
+    pattern: &str,
+    group_index: usize,
+) -> Result<ArrayRef> {
+    regexp_extract_all_with(input, pattern, group_index, false)
+}
+
+/// Same as [`regexp_extract_all`], optionally with overlapping matches.
+///
+/// With `overlapping` the search resumes one character after the start of each match
+/// instead of after its end, so `AA` on `"AAAA"` yields three matches instead of two.
+///
+/// # Example
+/// ```
+/// use flarion_task::regexp_extract_all_with;
+/// use datafusion::arrow::array::{AsArray, StringArray};
+///
+/// let input = StringArray::from(vec!["AAAA"]);
+/// let result = regexp_extract_all_with(&input, "AA", 0, true).unwrap();
+/// assert_eq!(result.as_list::<i32>().value_length(0), 3);
+/// ```
+pub fn regexp_extract_all_with(
+    input: &StringArray,
+    pattern: &str,
+    group_index: usize,
+    overlapping: bool,
+) -> Result<ArrayRef> {
+    let re = compile_regex("regexp_extract_all", pattern)?;
+    let array: ListArray = extract_all(input, &re, group_index, overlapping);
+
+    Ok(Arc::new(array))
+}
+
+/// Same as [`regexp_extract_all`], but returning a `LargeList` of `LargeUtf8`.
+///
+/// Both the list and the string offsets are 64-bit, so a batch whose matches add up to
+/// more than `i32::MAX` values or bytes does not overflow them.
+///
+/// # Example
+/// ```
+/// use flarion_task::regexp_extract_all_large;
+/// use datafusion::arrow::array::{Array, AsArray, LargeStringArray, StringArray};
+///
+/// let input = StringArray::from(vec![Some("a1b22c333"), None]);
+/// let result = regexp_extract_all_large(&input, r"(\d+)", 1).unwrap();
+/// let result_array = result.as_list::<i64>();
+///
+/// let first = result_array.value(0);
+/// assert_eq!(first.as_string::<i64>(), &LargeStringArray::from(vec!["1", "22", "333"]));
+/// assert!(result_array.is_null(1));
+/// ```
+pub fn regexp_extract_all_large(
+    input: &StringArray,
+    pattern: &str,
+    group_index: usize,
+) -> Result<ArrayRef> {
+    let re = compile_regex("regexp_extract_all_large", pattern)?;
+    let array: LargeListArray = extract_all(input, &re, group_index, false);
+
+    Ok(Arc::new(array))
+}
+
+/// Builds the lists of [`regexp_extract_all_with`] with `O` sized list and string offsets.
+fn extract_all<O: OffsetSizeTrait>(
+    input: &StringArray,
+    re: &Regex,
+    group_index: usize,
+    overlapping: bool,
+) -> GenericListArray<O> {
+    let mut builder = GenericListBuilder::<O, _>::new(GenericStringBuilder::<O>::new());
+    for optional_data in input.iter() {
+        match optional_data {
+            Some(data) => {
+                for captures in all_captures(re, data, overlapping) {
+                    let value = captures.get(group_index).map_or("", |m| m.as_str());
+                    builder.values().append_value(value);
+                }
+                builder.append(true);
+            }
+            None => builder.append(false),
+        }
+    }
+
+    builder.finish()
+}
+
+/// Counts the distinct matches in each list of a [`regexp_extract_all`] result.
+///
+/// Null matches inside a list are not counted.
+///
+/// # Arguments
+/// * `list` - `List(Utf8)` array of matches per row
+///
+/// # Returns
+/// * `Result<ArrayRef>` - Arrow `UInt32Array` of distinct match counts.
+///   Null lists produce nulls, empty lists produce 0.
+///
+/// # Example
+/// ```
+/// use flarion_task::{distinct_match_count, regexp_extract_all};
+/// use datafusion::arrow::array::{AsArray, StringArray};
+/// use datafusion::arrow::datatypes::UInt32Type;
+///
+/// let input = StringArray::from(vec!["a1b2c1", "xyz"]);
+/// let matches = regexp_extract_all(&input, r"\d", 0).unwrap();
+/// let result = distinct_match_count(matches.as_list::<i32>()).unwrap();
+///
+/// assert_eq!(result.as_primitive::<UInt32Type>().values(), &[2, 0]);
+/// ```
+pub fn distinct_match_count(list: &ListArray) -> Result<ArrayRef> {
+    let values = list.values().as_string_opt::<i32>().ok_or_else(|| {
+        unsupported_input("distinct_match_count", "a List(Utf8)", list.data_type())
+    })?;
+
+    let array: UInt32Array = list
+        .value_offsets()
+        .windows(2)
+        .enumerate()
+        .map(|(row, offsets)| {
+            list.is_valid(row).then(|| {
+                let matches = (offsets[0] as usize..offsets[1] as usize)
+                    .filter(|&i| values.is_valid(i))
+                    .map(|i| values.value(i))
+                    .collect::<HashSet<_>>();
+                matches.len() as u32
+            })
+        })
+        .collect();
+
+    Ok(Arc::new(array))
+}
+
+/// Creates a DataFusion UDF that extracts a capture group from every match of a regular expression pattern.
+///
+/// # Returns
+/// * `ScalarUDF` - User Defined Function that accepts:
+///   - input: string array to process
+///   - pattern: regex pattern string
+///   - group_index: capture group index (as UInt32).
+pub fn create_regexp_extract_all() -> ScalarUDF {
+    // Also resolvable by its uppercase Spark name
+    regex_udf!(
+        "regexp_extract_all" -> DataType::List(Arc::new(Field::new("item", DataType::Utf8, true))),
+        |input, pattern, group_index: UInt32| regexp_extract_all(input, pattern, group_index)
+    )
+    .with_aliases(["REGEXP_EXTRACT_ALL"])
+}
+
+/// Extracts every capture group of every match of a regular expression pattern.
+///
+/// Combines [`regexp_extract_all`] and [`regexp_extract_struct`]: each row yields a list
+/// with one struct per match, holding that match's capture groups.
+///
+/// # Arguments
+/// * `input` - Input string array to process
+/// * `pattern` - Regular expression pattern to match
+///
+/// # Returns
+/// * `Result<ArrayRef>` - Arrow `ListArray` of `StructArray` with one `Utf8` field per
+///   capture group, named as in [`regexp_extract_struct`]. Groups that did not take part
+///   in a match yield empty strings, null inputs yield a null list and rows without
+///   matches an empty list.
+///
+/// # Example
+/// ```
+/// use flarion_task::regexp_extract_all_groups;
+/// use datafusion::arrow::array::{Array, ListArray, StringArray, StructArray};
+///
+/// let input = StringArray::from(vec![Some("a=1 b=2"), None]);
+/// let result = regexp_extract_all_groups(&input, r"(?P<key>\w)=(\d)").unwrap();
+/// let result_array = result.as_any().downcast_ref::<ListArray>().unwrap();
+///
+/// let matches = result_array.value(0);
+/// let matches = matches.as_any().downcast_ref::<StructArray>().unwrap();
+/// let keys = matches.column_by_name("key").unwrap();
+/// let values = matches.column_by_name("g2").unwrap();
+/// assert_eq!(keys.as_ref(), &StringArray::from(vec!["a", "b"]));
+/// assert_eq!(values.as_ref(), &StringArray::from(vec!["1", "2"]));
+/// assert!(result_array.is_null(1));
+/// ```
+pub fn regexp_extract_all_groups(input: &StringArray, pattern: &str) -> Result<ArrayRef> {
+    regexp_extract_all_groups_with(input, pattern, false)
+}
+
+/// Same as [`regexp_extract_all_groups`], optionally with overlapping matches as in
+/// [`regexp_extract_all_with`].
+pub fn regexp_extract_all_groups_with(
+    input: &StringArray,
+    pattern: &str,
+    overlapping: bool,
+) -> Result<ArrayRef> {
+    let re = compile_regex("regexp_extract_all_groups", pattern)?;
+    let fields = group_fields(&re);
+
+    let mut builders: Vec<StringBuilder> = fields.iter().map(|_| StringBuilder::new()).collect();
+    let mut matches = 0;
+    let mut offsets: Vec<i32> = Vec::with_capacity(input.len() + 1);
+    offsets.push(0);
+    for optional_data in input.iter() {
+        if let Some(data) = optional_data {
+            for captures in all_captures(&re, data, overlapping) {
+                for (i, builder) in builders.iter_mut().enumerate() {
+                    builder.append_value(captures.get(i + 1).map_or("", |m| m.as_str()));
+                }
+                matches += 1;
+            }
+        }
+        offsets.push(int32_offset("regexp_extract_all_groups", matches)?);
+    }
+
+    let values = if fields.is_empty() {
+        StructArray::new_empty_fields(matches, None)
+    } else {
+        let columns: Vec<ArrayRef> = builders
+            .iter_mut()
+            .map(|builder| Arc::new(builder.finish()) as ArrayRef)
+            .collect();
+        StructArray::try_new(fields.clone(), columns, None)?
+    };
+
+    Ok(Arc::new(ListArray::try_new(
+        Arc::new(Field::new("item", DataType::Struct(fields), true)),
+        OffsetBuffer::new(offsets.into()),
+        Arc::new(values),
+        input.nulls().cloned(),
+    )?))
+}
+
+/// DataFusion implementation of [`regexp_extract_all_groups`] as a [`ScalarUDFImpl`].
+///
+/// The struct fields depend on the capture groups of the pattern, so the pattern has to
+/// be a constant known while planning.
+#[derive(Debug)]
+pub struct RegexpExtractAllGroups {
+    signature: Signature,
+}
+
+impl RegexpExtractAllGroups {
+    /// Creates the UDF implementation.
+    pub fn new() -> Self {
+        let input_types = vec![
+            DataType::Utf8, // First input type: StringArray (Utf8)
+            DataType::Utf8, // Second input type: String (Pattern)
+        ];
+        Self {
+            signature: Signature::exact(input_types, Volatility::Immutable),
+        }
+    }
+}
+
+impl Default for RegexpExtractAllGroups {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ScalarUDFImpl for RegexpExtractAllGroups {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "regexp_extract_all_groups"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        Err(
+            unsupported_input(self.name(), "a constant pattern", &arg_types[1])
+                .context("Error during planning"),
+        )
+    }
+
+    fn return_type_from_exprs(
+        &self,
+        args: &[Expr],
+        _schema: &dyn ExprSchema,
+        arg_types: &[DataType],
+    ) -> Result<DataType> {
+        let Some(Expr::Literal(ScalarValue::Utf8(Some(pattern)))) = args.get(1) else {
+            return self.return_type(arg_types);
+        };
+        let re = compile_regex(self.name(), pattern)?;
+        let fields = group_fields(&re);
+        Ok(DataType::new_list(DataType::Struct(fields), true))
+    }
+
+    fn invoke_batch(&self, args: &[ColumnarValue], _number_rows: usize) -> Result<ColumnarValue> {
+        check_arity(self.name(), args, 2)?;
+        let input = string_array_arg(self.name(), &args[0])?;
+        let pattern = string_arg(self.name(), args.get(1), "pattern")?;
+
+        Ok(ColumnarValue::Array(regexp_extract_all_groups(
+            input, pattern,
+        )?))
+    }
+}
+
+/// Creates a DataFusion UDF that extracts every capture group of every match of a regular expression pattern.
+///
+/// # Returns
+/// * `ScalarUDF` - User Defined Function that accepts:
+///   - input: string array to process
+///   - pattern: constant regex pattern string
+///
+///   and returns a list of structs, one per match, that can be flattened with `UNNEST`.
+pub fn create_regexp_extract_all_groups() -> ScalarUDF {
+    ScalarUDF::new_from_impl(RegexpExtractAllGroups::new())
+}
+
+/// Returns the capture groups of the first match, or of every match with the `g` flag,
+/// like Postgres' `regexp_matches`.
+///
+/// A pattern without capture groups yields the whole match instead, and a group that does
+/// not participate in a match yields a null element.
+///
+/// # Arguments
+/// * `input` - Input string array to process
+/// * `pattern` - Regular expression pattern to match
+/// * `flags` - `g` to return every match, combined with any of the regex flags `i`, `m`,
+///   `s`, `x`, `R` and `U`
+///
+/// # Returns
+/// * `Result<ArrayRef>` - Without `g`, a `List(Utf8)` array holding the groups of the first
+///   match, null for rows the pattern does not match. With `g`, a `List(List(Utf8))` array
+///   holding the groups of every match, empty for rows the pattern does not match. Null
+///   inputs yield null rows either way.
+///
+/// # Example
+/// ```
+/// use flarion_task::regexp_matches;
+/// use datafusion::arrow::array::{Array, AsArray, StringArray};
+///
+/// let input = StringArray::from(vec![Some("foobarbequebazilbarfbonk")]);
+/// let result = regexp_matches(&input, r"(b[^b]+)(b[^b]+)", "g").unwrap();
+/// let matches = result.as_list::<i32>().value(0);
+/// let matches = matches.as_list::<i32>();
+///
+/// assert_eq!(matches.len(), 2);
+/// assert_eq!(matches.value(0).as_string::<i32>(), &StringArray::from(vec!["bar", "beque"]));
+/// assert_eq!(matches.value(1).as_string::<i32>(), &StringArray::from(vec!["bazil", "barf"]));
+/// ```
+pub fn regexp_matches(input: &StringArray, pattern: &str, flags: &str) -> Result<ArrayRef> {
+    // `g` is the only flag of its own, the others are the regex flags of `build_regex`
+    let regex_flags: String = flags.chars().filter(|flag| *flag != 'g').collect();
+    check_flags("regexp_matches", &regex_flags)?;
+    let re = compile_regex_with_flags("regexp_matches", pattern, &regex_flags)?;
+
+    if !flags.contains('g') {
+        let mut builder = ListBuilder::new(StringBuilder::new());
+        for optional_data in input.iter() {
+            match optional_data.and_then(|data| re.captures(data)) {
+                Some(captures) => {
+                    append_match_groups(builder.values(), &captures);
+                    builder.append(true);
+                }
+                None => builder.append(false),
+            }
+        }
+        return Ok(Arc::new(builder.finish()));
+    }
+
+    let mut builder = ListBuilder::new(ListBuilder::new(StringBuilder::new()));
+    for optional_data in input.iter() {
+        match optional_data {
+            Some(data) => {
+                for captures in all_captures(&re, data, false) {
+                    append_match_groups(builder.values().values(), &captures);
+                    builder.values().append(true);
+                }
+                builder.append(true);
+            }
+            None => builder.append(false),
+        }
+    }
+
+    Ok(Arc::new(builder.finish()))
+}
+
+/// Appends the capture groups of a match, or the whole match for a pattern without groups.
+fn append_match_groups(builder: &mut StringBuilder, captures: &regex::Captures) {
+    if captures.len() == 1 {
+        builder.append_value(&captures[0]);
+        return;
+    }
+    for group in captures.iter().skip(1) {
+        builder.append_option(group.map(|m| m.as_str()));
+    }
+}
+
+/// DataFusion implementation of [`regexp_matches`] as a [`ScalarUDFImpl`].
+///
+/// The flags decide the return type, so they must be a constant: `List(Utf8)` without the
+/// `g` flag and `List(List(Utf8))` with it.
+#[derive(Debug)]
+pub struct RegexpMatches {
+    signature: Signature,
+}
+
+impl RegexpMatches {
+    /// Creates the UDF implementation.
+    pub fn new() -> Self {
+        let type_signatures = vec![
+            TypeSignature::Exact(vec![
+                DataType::Utf8, // First input type: StringArray (Utf8)
+                DataType::Utf8, // Second input type: String (Pattern)
+            ]),
+            TypeSignature::Exact(vec![
+                DataType::Utf8, // First input type: StringArray (Utf8)
+                DataType::Utf8, // Second input type: String (Pattern)
+                DataType::Utf8, // Third input type: String (Flags)
+            ]),
+        ];
+        Self {
+            signature: Signature::one_of(type_signatures, Volatility::Immutable),
+        }
+    }
+}
+
+impl Default for RegexpMatches {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ScalarUDFImpl for RegexpMatches {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "regexp_matches"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        if arg_types.len() > 2 {
+            return Err(
+                unsupported_input(self.name(), "constant flags", &arg_types[2])
+                    .context("Error during planning"),
+            );
+        }
+        Ok(DataType::new_list(DataType::Utf8, true))
+    }
+
+    fn return_type_from_exprs(
+        &self,
+        args: &[Expr],
+        _schema: &dyn ExprSchema,
+        arg_types: &[DataType],
+    ) -> Result<DataType> {
+        match args.get(2) {
+            Some(Expr::Literal(ScalarValue::Utf8(Some(flags)))) if flags.contains('g') => Ok(
+                DataType::new_list(DataType::new_list(DataType::Utf8, true), true),
+            ),
+            Some(Expr::Literal(ScalarValue::Utf8(Some(_)))) => {
+                Ok(DataType::new_list(DataType::Utf8, true))
+            }
+            _ => self.return_type(arg_types),
+        }
+    }
+
+    fn invoke_batch(&self, args: &[ColumnarValue], _number_rows: usize) -> Result<ColumnarValue> {
+        check_arity_range(self.name(), args, 2, 3)?;
+        let input = string_array_arg(self.name(), &args[0])?;
+        let pattern = string_arg(self.name(), args.get(1), "pattern")?;
+        let flags = match args.get(2) {
+            Some(_) => string_arg(self.name(), args.get(2), "flags")?,
+            None => "",
+        };
+
+        Ok(ColumnarValue::Array(regexp_matches(input, pattern, flags)?))
+    }
+}
+
+/// Creates a DataFusion UDF that returns the capture groups of the first or every match of a regular expression pattern.
+///
+/// # Returns
+/// * `ScalarUDF` - User Defined Function that accepts:
+///   - input: string array to process
+///   - pattern: regex pattern string
+///   - flags: optional constant flags string, `g` to return every match
+///
+///   and returns a list of groups, or with `g` a list of them per match.
+pub fn create_regexp_matches() -> ScalarUDF {
+    ScalarUDF::new_from_impl(RegexpMatches::new())
+}
+
+/// Tests whether strings match a regular expression pattern anywhere.
+///
+/// # Arguments
+/// * `input` - Input string array to process
+/// * `pattern` - Regular expression pattern to match
+///
+/// # Returns
+/// * `Result<ArrayRef>` - Arrow `BooleanArray`, true where the pattern matches, or nulls
+///
+/// # Example
+/// ```
+/// use flarion_task::regexp_like;
+/// use datafusion::arrow::array::{Array, BooleanArray, StringArray};
+///
+/// let input = StringArray::from(vec![Some("abc123"), Some("abc"), None]);
+/// let result = regexp_like(&input, r"\d+").unwrap();
+/// let result_array = result.as_any().downcast_ref::<BooleanArray>().unwrap();
+///
+/// assert!(result_array.value(0));
+/// assert!(!result_array.value(1));
+/// assert!(result_array.is_null(2));
+/// ```
+pub fn regexp_like(input: &StringArray, pattern: &str) -> Result<ArrayRef> {
+    let re = compile_regex("regexp_like", pattern)?;
+
+    let array: BooleanArray = input
+        .iter()
+        .map(|optional_data| optional_data.map(|data| re.is_match(data)))
+        .collect();
+
+    Ok(Arc::new(array))
+}
+
+/// Creates a DataFusion UDF that tests whether strings match a regular expression pattern.
+///
+/// # Returns
+/// * `ScalarUDF` - User Defined Function that accepts:
+///   - input: string array to process
+///   - pattern: regex pattern string.
+///
+/// The function is also registered under the alias `rlike`.
+pub fn create_regexp_like() -> ScalarUDF {
+    // Also resolvable as Spark's `rlike` and `regexp`
+    regex_udf!(
+        "regexp_like" -> DataType::Boolean,
+        |input, pattern| regexp_like(input, pattern)
+    )
+    .with_aliases(["rlike", "regexp", "REGEXP_LIKE", "RLIKE", "REGEXP"])
+}
+
+/// Iterates over the non-overlapping matches of `re` in `haystack` the way Java's
+/// `Matcher.find` loop (and therefore Spark) does.
+///
+/// Unlike `Regex::find_iter`, an empty match directly following a non-empty match is
+/// reported. After an empty match the search resumes one character further, so the
+/// iteration always terminates.
+fn spark_find_iter<'a>(re: &'a Regex, haystack: &'a str) -> impl Iterator<Item = Match<'a>> + 'a {
+    let mut start = Some(0);
+    std::iter::from_fn(move || {
+        let m = re.find_at(haystack, start?)?;
+        start = spark_next_start(haystack, m);
+        Some(m)
+    })
+}
+
+/// Offset at which [`spark_find_iter`] searches for the match after `m`, `None` past the
+/// end of `haystack`.
+fn spark_next_start(haystack: &str, m: Match) -> Option<usize> {
+    if m.is_empty() {
+        haystack[m.end()..]
+            .chars()
+            .next()
+            .map(|c| m.end() + c.len_utf8())
+    } else {
+        Some(m.end())
+    }
+}
+
+/// Counts the non-overlapping matches of a regular expression pattern in strings.
+///
+/// Zero-width matches are counted once per position, so an empty pattern matches
+/// `n + 1` times in a string of `n` characters (same as Spark's `regexp_count`).
+///
+/// # Arguments
+/// * `input` - Input string array to process
+/// * `pattern` - Regular expression pattern to match
+///
+/// # Returns
+/// * `Result<ArrayRef>` - Arrow `Int64Array` containing match counts or nulls
+///
+/// # Example
+/// ```
+/// use flarion_task::regexp_count;
+/// use datafusion::arrow::array::{Array, Int64Array, StringArray};
+///
+/// let input = StringArray::from(vec![Some("a1b22c333"), None]);
+/// let result = regexp_count(&input, r"\d+").unwrap();
+/// let result_array = result.as_any().downcast_ref::<Int64Array>().unwrap();
+///
+/// assert_eq!(result_array.value(0), 3);
+/// assert!(result_array.is_null(1));
+/// ```
+pub fn regexp_count(input: &StringArray, pattern: &str) -> Result<ArrayRef> {
+    let re = compile_regex("regexp_count", pattern)?;
+
+    let array: Int64Array = input
+        .iter()
+        .map(|optional_data| optional_data.map(|data| spark_find_iter(&re, data).count() as i64))
+        .collect();
+
+    Ok(Arc::new(array))
+}
+
+/// Creates a DataFusion UDF that counts the matches of a regular expression pattern in strings.
+///
+/// # Returns
+/// * `ScalarUDF` - User Defined Function that accepts:
+///   - input: string array to process
+///   - pattern: regex pattern string.
+pub fn create_regexp_count() -> ScalarUDF {
+    regex_udf!(
+        "regexp_count" -> DataType::Int64,
+        |input, pattern| regexp_count(input, pattern)
+    )
+}
+
+/// Returns the 1-based character position of the first match of a regular expression pattern.
+///
+/// Positions are counted in characters, not bytes, so multi-byte UTF-8 input yields the
+/// same offsets as Spark's `regexp_instr`.
+///
+/// # Arguments
+/// * `input` - Input string array to process
+/// * `pattern` - Regular expression pattern to match
+///
+/// # Returns
+/// * `Result<ArrayRef>` - Arrow `Int32Array` of positions (0 when there is no match) or nulls
+///
+/// # Example
+/// ```
+/// use flarion_task::regexp_instr;
+/// use datafusion::arrow::array::{Array, Int32Array, StringArray};
+///
+/// let input = StringArray::from(vec![Some("abc123"), Some("abc"), None]);
+/// let result = regexp_instr(&input, r"\d+").unwrap();
+/// let result_array = result.as_any().downcast_ref::<Int32Array>().unwrap();
+///
+/// assert_eq!(result_array.value(0), 4);
+/// assert_eq!(result_array.value(1), 0);
+/// assert!(result_array.is_null(2));
+/// ```
+pub fn regexp_instr(input: &StringArray, pattern: &str) -> Result<ArrayRef> {
+    let re = compile_regex("regexp_instr", pattern)?;
+
+    let array: Int32Array = input
+        .iter()
+        .map(|optional_data| {
+            optional_data
+                .map(|data| match re.find(data) {
+                    Some(m) => int32_offset("regexp_instr", data[..m.start()].chars().count() + 1),
+                    None => Ok(0),
+                })
+                .transpose()
+        })
+        .collect::<Result<_>>()?;
+
+    Ok(Arc::new(array))
+}
+
+/// Creates a DataFusion UDF that returns the 1-based character position of the first match.
+///
+/// # Returns
+/// * `ScalarUDF` - User Defined Function that accepts:
+///   - input: string array to process
+///   - pattern: regex pattern string.
+pub fn create_regexp_instr() -> ScalarUDF {
+    regex_udf!(
+        "regexp_instr" -> DataType::Int32,
+        |input, pattern| regexp_instr(input, pattern)
+    )
+}
+
+/// Returns the `occurrence`-th match of a regular expression pattern, searching from a
+/// character position, like Oracle's `REGEXP_SUBSTR`.
+///
+/// Matches are non-overlapping and counted from `position`, so `regexp_substr` complements
+/// [`regexp_instr`] for extracting the match itself rather than its position.
+///
+/// # Arguments
+/// * `input` - Input string array to process
+/// * `pattern` - Regular expression pattern to match
+/// * `position` - 1-based character position the search starts at
+/// * `occurrence` - 1-based number of the match to return
+///
+/// # Returns
+/// * `Result<ArrayRef>` - Arrow `StringArray` with the full text of the requested match.
+///   Rows with fewer matches, or shorter than `position`, and null inputs yield nulls.
+///   Returns an error if `position` or `occurrence` is less than 1.
+///
+/// # Example
+/// ```
+/// use flarion_task::regexp_substr;
+/// use datafusion::arrow::array::{Array, StringArray};
+///
+/// let input = StringArray::from(vec![Some("a1b22c333"), Some("a1")]);
+/// let result = regexp_substr(&input, r"\d+", 1, 2).unwrap();
+/// let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+///
+/// assert_eq!(result_array.value(0), "22");
+/// assert!(result_array.is_null(1));
+/// ```
+pub fn regexp_substr(
+    input: &StringArray,
+    pattern: &str,
+    position: i32,
+    occurrence: i32,
+) -> Result<ArrayRef> {
+    for (argument, value) in [("position", position), ("occurrence", occurrence)] {
+        if value < 1 {
+            return Err(RegexpError::ArgumentOutOfRange {
+                function: "regexp_substr".to_string(),
+                argument: argument.to_string(),
+                value: value.into(),
+                min: 1,
+            }
+            .into());
+        }
+    }
+    let re = compile_regex("regexp_substr", pattern)?;
+    let (skip, occurrence) = (position as usize - 1, occurrence as usize - 1);
+
+    let mut builder = StringBuilder::with_capacity(input.len(), 0);
+    for optional_data in input.iter() {
+        // Byte offset of the character at `position`
+        let start = optional_data.and_then(|data| {
+            data.char_indices()
+                .nth(skip)
+                .map(|(start, _)| (data, start))
+        });
+        let value = start.and_then(|(data, start)| {
+            spark_find_iter(&re, &data[start..])
+                .nth(occurrence)
+                .map(|m| m.as_str())
+        });
+        builder.append_option(value);
+    }
+
+    Ok(Arc::new(builder.finish()))
+}
+
+/// Creates a DataFusion UDF that returns the requested occurrence of a regular expression pattern.
+///
+/// # Returns
+/// * `ScalarUDF` - User Defined Function that accepts:
+///   - input: string array to process
+///   - pattern: regex pattern string
+///   - position: 1-based character position to search from (as Int64)
+///   - occurrence: 1-based number of the match to return (as Int64).
+///
+///   A null pattern, position or occurrence yields all null rows.
+pub fn create_regexp_substr() -> ScalarUDF {
+    let input_types = vec![
+        DataType::Utf8,  // StringArray (input)
+        DataType::Utf8,  // String (pattern)
+        DataType::Int64, // Int64 (position)
+        DataType::Int64, // Int64 (occurrence)
+    ];
+
+    let fun: ScalarFunctionImplementation =
+        Arc::new(|args: &[ColumnarValue]| -> Result<ColumnarValue> {
+            check_arity("regexp_substr", args, 4)?;
+            let input = string_array_arg("regexp_substr", &args[0])?;
+
+            let pattern = match &args[1] {
+                ColumnarValue::Scalar(scalar) => pattern_from_scalar("regexp_substr", scalar)?,
+                ColumnarValue::Array(patterns) => {
+                    return Err(unsupported_input(
+                        "regexp_substr",
+                        "a constant pattern",
+                        patterns.data_type(),
+                    ))
+                }
+            };
+            let position = int64_arg("regexp_substr", args.get(2), "position")?;
+            let occurrence = int64_arg("regexp_substr", args.get(3), "occurrence")?;
+
+            // Like regexp_extract, a null pattern, position or occurrence makes every row null
+            let (Some(pattern), Some(position), Some(occurrence)) = (pattern, position, occurrence)
+            else {
+                return Ok(ColumnarValue::Array(new_null_array(
+                    &DataType::Utf8,
+                    input.len(),
+                )));
+            };
+            Ok(ColumnarValue::Array(regexp_substr(
+                input, pattern, position, occurrence,
+            )?))
+        });
+
+    // Mark as immutable (does not depend on the data)
+    create_udf(
+        "regexp_substr",
+        input_types,
+        DataType::Utf8,
+        Volatility::Immutable,
+        fun,
+    )
+}
+
+/// Converts a character offset or count to the `Int32` it is returned as, failing
+/// instead of wrapping around for values longer than `i32::MAX` characters.
+fn int32_offset(udf_name: &str, offset: usize) -> Result<i32> {
+    i32::try_from(offset).map_err(|_| {
+        RegexpError::OffsetOverflow {
+            function: udf_name.to_string(),
+            offset,
+        }
+        .into()
+    })
+}
+
+/// Returns the character offsets of every non-overlapping match of a regular expression
+/// pattern in strings.
+///
+/// Offsets count characters rather than bytes, so they can be used to slice the string
+/// correctly even for multi-byte UTF-8 input. `start` is 0-based and `end` is exclusive.
+///
+/// # Arguments
+/// * `input` - Input string array to process
+/// * `pattern` - Regular expression pattern to match
+///
+/// # Returns
+/// * `Result<ArrayRef>` - Arrow `ListArray` of `StructArray { start: Int32, end: Int32 }`
+///   with one entry per match, and a null list for null input
+///
+/// # Example
+/// ```
+/// use flarion_task::regexp_match_offsets;
+/// use datafusion::arrow::array::{Array, Int32Array, ListArray, StringArray, StructArray};
+///
+/// let input = StringArray::from(vec![Some("a1b22"), None]);
+/// let result = regexp_match_offsets(&input, r"\d+").unwrap();
+/// let result_array = result.as_any().downcast_ref::<ListArray>().unwrap();
+///
+/// let matches = result_array.value(0);
+/// let matches = matches.as_any().downcast_ref::<StructArray>().unwrap();
+/// let start = matches.column_by_name("start").unwrap();
+/// let end = matches.column_by_name("end").unwrap();
+/// assert_eq!(start.as_ref(), &Int32Array::from(vec![1, 3]));
+/// assert_eq!(end.as_ref(), &Int32Array::from(vec![2, 5]));
+/// assert!(result_array.is_null(1));
+/// ```
+pub fn regexp_match_offsets(input: &StringArray, pattern: &str) -> Result<ArrayRef> {
+    let re = compile_regex("regexp_match_offsets", pattern)?;
+
+    let mut starts: Vec<i32> = vec![];
+    let mut ends: Vec<i32> = vec![];
+    let mut offsets: Vec<i32> = Vec::with_capacity(input.len() + 1);
+    offsets.push(0);
+    for optional_data in input.iter() {
+        if let Some(data) = optional_data {
+            // Characters are counted incrementally from the end of the previous match
+            let (mut chars, mut bytes) = (0, 0);
+            for m in spark_find_iter(&re, data) {
+                chars += data[bytes..m.start()].chars().count();
+                starts.push(int32_offset("regexp_match_offsets", chars)?);
+                chars += m.as_str().chars().count();
+                ends.push(int32_offset("regexp_match_offsets", chars)?);
+                bytes = m.end();
+            }
+        }
+        offsets.push(int32_offset("regexp_match_offsets", starts.len())?);
+    }
+
+    let fields = Fields::from(vec![
+        Field::new("start", DataType::Int32, false),
+        Field::new("end", DataType::Int32, false),
+    ]);
+    let values = StructArray::try_new(
+        fields.clone(),
+        vec![
+            Arc::new(Int32Array::from(starts)),
+            Arc::new(Int32Array::from(ends)),
+        ],
+        None,
+    )?;
+
+    Ok(Arc::new(ListArray::try_new(
+        Arc::new(Field::new("item", DataType::Struct(fields), true)),
+        OffsetBuffer::new(offsets.into()),
+        Arc::new(values),
+        input.nulls().cloned(),
+    )?))
+}
+
+/// Splits strings around the matches of a regular expression pattern.
+///
+/// Every row's list holds the pieces between matches. Leading and trailing empty
+/// pieces are kept, so `"a,b,"` split on `,` yields `["a", "b", ""]`. Zero-width matches
+/// split between characters, e.g. an empty pattern turns `"ab"` into `["", "a", "b", ""]`.
+///
+/// # Arguments
+/// * `input` - Input string array to process
+/// * `pattern` - Regular expression pattern matching the delimiters
+///
+/// # Returns
+/// * `Result<ArrayRef>` - Arrow `ListArray` of strings, with a null list for null input
+///
+/// # Example
+/// ```
+/// use flarion_task::regexp_split;
+/// use datafusion::arrow::array::{Array, ListArray, StringArray};
+///
+/// let input = StringArray::from(vec![Some("a1b22c"), None]);
+/// let result = regexp_split(&input, r"\d+").unwrap();
+/// let result_array = result.as_any().downcast_ref::<ListArray>().unwrap();
+///
+/// let pieces = result_array.value(0);
+/// let pieces = pieces.as_any().downcast_ref::<StringArray>().unwrap();
+/// assert_eq!(pieces, &StringArray::from(vec!["a", "b", "c"]));
+/// assert!(result_array.is_null(1));
+/// ```
+pub fn regexp_split(input: &StringArray, pattern: &str) -> Result<ArrayRef> {
+    let re = compile_regex("regexp_split", pattern)?;
+
+    Ok(Arc::new(split_with_regex(input, &re, None)))
+}
+
+/// Splits strings around the matches of a regular expression pattern into at most `limit` pieces.
+///
+/// Like Spark's `split(str, regex, limit)`:
+///
+/// * `limit > 0` - the list holds at most `limit` pieces, the last one containing the
+///   rest of the string unsplit
+/// * `limit <= 0` - the pattern is applied as many times as possible, same as [`regexp_split`]
+///
+/// Trailing empty pieces are never removed, Spark always splits with a negative
+/// Java limit.
+///
+/// # Arguments
+/// * `input` - Input string array to process
+/// * `pattern` - Regular expression pattern matching the delimiters
+/// * `limit` - Maximum number of pieces per row, or `<= 0` for no limit
+///
+/// # Returns
+/// * `Result<ArrayRef>` - Arrow `ListArray` of strings, with a null list for null input
+///
+/// # Example
+/// ```
+/// use flarion_task::regexp_split_limit;
+/// use datafusion::arrow::array::{Array, ListArray, StringArray};
+///
+/// let input = StringArray::from(vec!["a,b,c"]);
+/// let result = regexp_split_limit(&input, ",", 2).unwrap();
+/// let result_array = result.as_any().downcast_ref::<ListArray>().unwrap();
+///
+/// let pieces = result_array.value(0);
+/// let pieces = pieces.as_any().downcast_ref::<StringArray>().unwrap();
+/// assert_eq!(pieces, &StringArray::from(vec!["a", "b,c"]));
+/// ```
+pub fn regexp_split_limit(input: &StringArray, pattern: &str, limit: i32) -> Result<ArrayRef> {
+    let re = compile_regex("regexp_split_limit", pattern)?;
+    let limit = usize::try_from(limit).ok().filter(|limit| *limit > 0);
+
+    Ok(Arc::new(split_with_regex(input, &re, limit)))
+}
+
+/// Splits every row around the matches of `re`, into at most `limit` pieces if given.
+fn split_with_regex(input: &StringArray, re: &Regex, limit: Option<usize>) -> ListArray {
+    let mut builder = ListBuilder::new(StringBuilder::new());
+    for optional_data in input.iter() {
+        match optional_data {
+            Some(data) => {
+                for piece in re.splitn(data, limit.unwrap_or(usize::MAX)) {
+                    builder.values().append_value(piece);
+                }
+                builder.append(true);
+            }
+            None => builder.append(false),
+        }
+    }
+
+    builder.finish()
+}
+
+/// Creates a DataFusion UDF that splits strings around the matches of a regular expression pattern.
+///
+/// # Returns
+/// * `ScalarUDF` - User Defined Function that accepts:
+///   - input: string array to process
+///   - pattern: regex pattern string.
+pub fn create_regexp_split() -> ScalarUDF {
+    // Also resolvable as Spark's `split`
+    regex_udf!(
+        "regexp_split" -> DataType::List(Arc::new(Field::new("item", DataType::Utf8, true))),
+        |input, pattern| regexp_split(input, pattern)
+    )
+    .with_aliases(["split"])
+}
+
+/// Registers every UDF provided by this crate into a DataFusion session.
+///
+/// Note that this replaces DataFusion's built-in `regexp_like`, `regexp_replace` and
+/// `regexp_count` with this crate's Spark-compatible versions.
+///
+/// # Example
+/// ```
+/// use datafusion::execution::FunctionRegistry;
+/// use datafusion::prelude::SessionContext;
+/// use flarion_task::register_all;
+///
+/// let ctx = SessionContext::new();
+/// register_all(&ctx);
+/// assert!(ctx.udf("regexp_extract").is_ok());
+/// ```
+pub fn register_all(ctx: &SessionContext) {
+    for udf in all_udfs() {
+        ctx.register_udf(udf);
+    }
+}
+
+/// Registers every UDF provided by this crate into any [`FunctionRegistry`], such as a
+/// [`SessionState`](datafusion::execution::SessionState), without a [`SessionContext`].
+///
+/// Registers the same UDFs as [`register_all`], replacing previously registered
+/// functions with the same names.
+///
+/// # Example
+/// ```
+/// use datafusion::execution::FunctionRegistry;
+/// use datafusion::execution::SessionStateBuilder;
+/// use flarion_task::register_into;
+///
+/// let mut state = SessionStateBuilder::new().build();
+/// register_into(&mut state).unwrap();
+/// assert!(state.udf("regexp_extract").is_ok());
+/// ```
+pub fn register_into(registry: &mut dyn FunctionRegistry) -> Result<()> {
+    for udf in all_udfs() {
+        registry.register_udf(Arc::new(udf))?;
+    }
+    Ok(())
+}
+
+/// Every UDF provided by this crate, see [`register_all`].
+fn all_udfs() -> Vec<ScalarUDF> {
+    vec![
+        create_regexp_extract(),
+        create_regexp_extract_binary(),
+        create_regexp_extract_default(),
+        create_regexp_extract_flags(),
+        create_regexp_extract_named(),
+        create_regexp_extract_all(),
+        create_regexp_extract_all_groups(),
+        create_regexp_matches(),
+        create_regexp_replace(),
+        create_regexp_replace_first(),
+        create_regexp_replace_n(),
+        create_regexp_like(),
+        create_regexp_count(),
+        create_regexp_instr(),
+        create_regexp_substr(),
+        create_regexp_split(),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        all_udfs, build_regex, cast, compile_regex, create_regexp_extract,
+        create_regexp_extract_binary, create_regexp_extract_default, create_regexp_extract_with,
+        create_regexp_substr, distinct_match_count, group_count, int32_offset, invalid_pattern,
+        match_failed, named_groups, regex_builder, regexp_count, regexp_extract,
+        regexp_extract_all, regexp_extract_all_groups, regexp_extract_all_groups_with,
+        regexp_extract_all_large, regexp_extract_all_with, regexp_extract_anchored,
+        regexp_extract_default, regexp_extract_dictionary, regexp_extract_dyn,
+        regexp_extract_fixed_size_binary, regexp_extract_flags, regexp_extract_from,
+        regexp_extract_generic, regexp_extract_large, regexp_extract_longest, regexp_extract_named,
+        regexp_extract_numeric, regexp_extract_opt, regexp_extract_pair, regexp_extract_rest,
+        regexp_extract_signature, regexp_extract_str, regexp_extract_str_with_config,
+        regexp_extract_strict, regexp_extract_struct, regexp_extract_view,
+        regexp_extract_view_to_view, regexp_extract_with_config, regexp_extract_with_flag,
+        regexp_extract_with_pos, regexp_instr, regexp_like, regexp_mask, regexp_match_offsets,
+        regexp_matches, regexp_replace, regexp_replace_first, regexp_replace_n,
+        regexp_replace_with, regexp_split, regexp_split_limit, regexp_substr, string_data_len,
+        validate_pattern, CaseTransform, CompiledExtractor, LruRegexCache, NoMatchBehavior,
+        RegexpError, RegexpExtract, RegexpExtractConfig, RegexpOptions,
+    };
+    use datafusion::arrow::array::{
+        Array, ArrayAccessor, ArrayRef, AsArray, BinaryArray, BooleanArray, DictionaryArray,
+        FixedSizeBinaryArray, Float64Array, Int32Array, Int64Array, LargeStringArray, ListArray,
+        ListBuilder, RunArray, StringArray, StringBuilder, StringViewArray, StructArray,
+        UInt32Array,
+    };
+    use datafusion::arrow::datatypes::{DataType, Field, Int32Type, Int8Type, UInt32Type};
+    use datafusion::logical_expr::{Expr, ScalarUDF, ScalarUDFImpl, TypeSignature, Volatility};
+    use datafusion::physical_plan::ColumnarValue;
+    use datafusion::scalar::ScalarValue;
+    use regex::Regex;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_regexp_extract_basic() {
+        let input = StringArray::from(vec![Some("hello123"), Some("world456"), None]);
+        let pattern = r"([a-z]+)(\d+)";
+
+        // Test group 0 (full match)
+        let result = regexp_extract(&input, pattern, 0).unwrap();
+        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(result_array.value(0), "hello123");
+        assert_eq!(result_array.value(1), "world456");
+        assert!(result_array.is_null(2));
+
+        // Test group 1 (letters)
+        let result = regexp_extract(&input, pattern, 1).unwrap();
+        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(result_array.value(0), "hello");
+        assert_eq!(result_array.value(1), "world");
+        assert!(result_array.is_null(2));
+
+        // Test group 2 (numbers)
+        let result = regexp_extract(&input, pattern, 2).unwrap();
+        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(result_array.value(0), "123");
+        assert_eq!(result_array.value(1), "456");
+        assert!(result_array.is_null(2));
+    }
+
+    #[test]
+    fn test_regexp_extract_no_match() {
+        let input = StringArray::from(vec![Some("abc"), Some("def")]);
+        let pattern = r"\d+";
+        let result = regexp_extract(&input, pattern, 0).unwrap();
+        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+        // No match should return empty string (Same as Spark behavior).
+        assert_eq!(result_array.value(0), "");
+        assert_eq!(result_array.value(1), "");
+    }
+
+    #[test]
+    fn test_regexp_extract_invalid_group() {
+        let input = StringArray::from(vec![Some("test123")]);
+        let pattern = r"(\w+)";
+        let result = regexp_extract(&input, pattern, 99).unwrap();
+        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(result_array.value(0), ""); // Invalid group index returns empty string (Same as Spark behavior).
+    }
+
+    #[test]
+    fn test_regexp_extract_optional_groups() {
+        let input = StringArray::from(vec![Some("abc123"), Some("def"), Some("456")]);
+        let pattern = r"([a-z]+)?(\d+)?";
+
+        // Test group 1 (letters)
+        let result = regexp_extract(&input, pattern, 1).unwrap();
+        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(result_array.value(0), "abc");
+        assert_eq!(result_array.value(1), "def");
+        assert_eq!(result_array.value(2), "");
+
+        // Test group 2 (numbers)
+        let result = regexp_extract(&input, pattern, 2).unwrap();
+        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(result_array.value(0), "123");
+        assert_eq!(result_array.value(1), "");
+        assert_eq!(result_array.value(2), "456");
+    }
+
+    #[test]
+    fn test_regexp_extract_special_chars() {
+        let input = StringArray::from(vec![Some("test.123"), Some("test-456"), Some("test_789")]);
+        let pattern = r"test(.)(\d+)";
+
+        // Test group 1 (special char)
+        let result = regexp_extract(&input, pattern, 1).unwrap();
+        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(result_array.value(0), ".");
+        assert_eq!(result_array.value(1), "-");
+        assert_eq!(result_array.value(2), "_");
+    }
+
+    #[test]
+    fn test_regexp_extract_empty_input() {
+        let input = StringArray::from(vec![Some("")]);
+        let pattern = r".*";
+        let result = regexp_extract(&input, pattern, 0).unwrap();
+        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(result_array.value(0), "");
+    }
+
+    #[test]
+    fn test_regexp_extract_number_range() {
+        let input = StringArray::from(vec![Some("100-200")]);
+        let result = regexp_extract(&input, r"(\d+)-(\d+)", 1).unwrap();
+        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(result_array.value(0), "100");
+    }
+
+    #[test]
+    fn test_regexp_extract_no_digits() {
+        let input = StringArray::from(vec![Some("foo")]);
+        let result = regexp_extract(&input, r"(\d+)", 1).unwrap();
+        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(result_array.value(0), "");
+    }
+
+    #[test]
+    fn test_regexp_extract_no_groups_full_match() {
+        let input = StringArray::from(vec![Some("abc123def"), Some("foo")]);
+        let result = regexp_extract(&input, r"\d+", 0).unwrap();
+        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(result_array.value(0), "123");
+        assert_eq!(result_array.value(1), "");
+    }
+
+    #[test]
+    fn test_regexp_extract_no_groups_index_past_end() {
+        let input = StringArray::from(vec![Some("abc123def")]);
+        let result = regexp_extract(&input, r"\d+", 1).unwrap();
+        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(result_array.value(0), "");
+
+        let config = RegexpExtractConfig {
+            no_match: NoMatchBehavior::Null,
+            missing_group_as_no_match: true,
+            ..RegexpExtractConfig::new(1)
+        };
+        let result = regexp_extract_with_config(&input, r"\d+", &config).unwrap();
+        assert!(result.is_null(0));
+    }
+
+    #[test]
+    fn test_regexp_extract_missing_optional_group() {
+        let input = StringArray::from(vec![Some("aaaac")]);
+        let result = regexp_extract(&input, "(a+)(b)?(c)", 2).unwrap();
+        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(result_array.value(0), "");
+    }
+
+    #[test]
+    fn test_regexp_replace_backreference() {
+        let input = StringArray::from(vec![Some("abc123def456"), Some("nodigits"), None]);
+        let result = regexp_replace(&input, r"(\d+)", "#$1").unwrap();
+        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(result_array.value(0), "abc#123def#456");
+        assert_eq!(result_array.value(1), "nodigits");
+        assert!(result_array.is_null(2));
+    }
+
+    #[test]
+    fn test_regexp_replace_named_group() {
+        let input = StringArray::from(vec![Some("2024-01-15")]);
+        let pattern = r"(?P<y>\d{4})-(?P<m>\d{2})-(?P<d>\d{2})";
+        let result = regexp_replace(&input, pattern, "${d}/${m}/${y}").unwrap();
+        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(result_array.value(0), "15/01/2024");
+    }
+
+    #[test]
+    fn test_regexp_replace_first() {
+        let input = StringArray::from(vec![Some("a1b2c3"), Some("nodigits"), None]);
+        let result = regexp_replace_first(&input, r"(\d)", "<$1>").unwrap();
+        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(result_array.value(0), "a<1>b2c3");
+        assert_eq!(result_array.value(1), "nodigits");
+        assert!(result_array.is_null(2));
+    }
+
+    #[test]
+    fn test_regexp_replace_n() {
+        let input = StringArray::from(vec![Some("a1b2c3"), None]);
+        let replace = |count| {
+            let result = regexp_replace_n(&input, r"\d", "X", count).unwrap();
+            let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+            assert!(result_array.is_null(1));
+            result_array.value(0).to_string()
+        };
+        assert_eq!(replace(1), "aXb2c3");
+        assert_eq!(replace(2), "aXbXc3");
+        // Zero and counts beyond the number of matches replace everything.
+        assert_eq!(replace(0), "aXbXcX");
+        assert_eq!(replace(10), "aXbXcX");
+    }
+
+    #[test]
+    fn test_regexp_replace_invalid_pattern() {
+        let input = StringArray::from(vec![Some("abc")]);
+        assert!(regexp_replace(&input, r"(", "x").is_err());
+    }
+
+    #[test]
+    fn test_regexp_extract_all_basic() {
+        let input = StringArray::from(vec![Some("a1b22c333"), Some("none"), None]);
+        let result = regexp_extract_all(&input, r"([a-z])(\d+)", 2).unwrap();
+        let result_array = result.as_any().downcast_ref::<ListArray>().unwrap();
+        assert_eq!(result_array.len(), 3);
+
+        let row = result_array.value(0);
+        let row = row.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(
+            row.iter().flatten().collect::<Vec<_>>(),
+            vec!["1", "22", "333"]
+        );
+
+        // No matches yields an empty (not null) list.
+        assert!(!result_array.is_null(1));
+        assert_eq!(result_array.value(1).len(), 0);
+
+        assert!(result_array.is_null(2));
+    }
+
+    #[test]
+    fn test_regexp_extract_all_optional_group() {
+        let input = StringArray::from(vec![Some("a1 b c2")]);
+        let result = regexp_extract_all(&input, r"([a-z])(\d)?", 2).unwrap();
+        let result_array = result.as_any().downcast_ref::<ListArray>().unwrap();
+        let row = result_array.value(0);
+        let row = row.as_any().downcast_ref::<StringArray>().unwrap();
+        // Non-participating groups yield empty strings, like regexp_extract.
+        assert_eq!(row.iter().flatten().collect::<Vec<_>>(), vec!["1", "", "2"]);
+    }
+
+    #[test]
+    fn test_regexp_like_basic() {
+        let input = StringArray::from(vec![Some("abc123"), Some("abc"), None, Some("")]);
+        let result = regexp_like(&input, r"\d").unwrap();
+        let result_array = result.as_any().downcast_ref::<BooleanArray>().unwrap();
+        assert!(result_array.value(0));
+        assert!(!result_array.value(1));
+        assert!(result_array.is_null(2));
+        assert!(!result_array.value(3));
+    }
+
+    #[test]
+    fn test_regexp_like_matches_anywhere() {
+        let input = StringArray::from(vec![Some("xx-abc-yy"), Some("ABC")]);
+        let result = regexp_like(&input, r"abc").unwrap();
+        let result_array = result.as_any().downcast_ref::<BooleanArray>().unwrap();
+        assert!(result_array.value(0));
+        assert!(!result_array.value(1));
+    }
+
+    #[test]
+    fn test_regexp_count_basic() {
+        let input = StringArray::from(vec![Some("a1b22c333"), Some("abc"), None]);
+        let result = regexp_count(&input, r"\d+").unwrap();
+        let result_array = result.as_any().downcast_ref::<Int64Array>().unwrap();
+        assert_eq!(result_array.value(0), 3);
+        assert_eq!(result_array.value(1), 0);
+        assert!(result_array.is_null(2));
+    }
+
+    #[test]
+    fn test_regexp_count_zero_width() {
+        let input = StringArray::from(vec![Some("abc"), Some(""), Some("baaab")]);
+
+        // An empty pattern matches at every position, including the end.
+        let result = regexp_count(&input, r"").unwrap();
+        let result_array = result.as_any().downcast_ref::<Int64Array>().unwrap();
+        assert_eq!(result_array.value(0), 4);
+        assert_eq!(result_array.value(1), 1);
+
+        // Matches "", "aaa", "" and "" (same as Java's Matcher.find loop).
+        let result = regexp_count(&input, r"a*").unwrap();
+        let result_array = result.as_any().downcast_ref::<Int64Array>().unwrap();
+        assert_eq!(result_array.value(2), 4);
+    }
+
+    #[test]
+    fn test_zero_width_matches_agree_across_functions() {
+        // Matches "", "aaa" and "" like Java's Matcher.find loop, whichever function asks.
+        let input = StringArray::from(vec!["baaa"]);
+
+        let result = regexp_count(&input, r"a*").unwrap();
+        let result_array = result.as_any().downcast_ref::<Int64Array>().unwrap();
+        assert_eq!(result_array.value(0), 3);
+
+        let result = regexp_extract_all(&input, r"a*", 0).unwrap();
+        let matches = result.as_list::<i32>().value(0);
+        assert_eq!(
+            matches.as_string::<i32>(),
+            &StringArray::from(vec!["", "aaa", ""])
+        );
+
+        let substrings: Vec<Option<String>> = (1..=4)
+            .map(|occurrence| {
+                let result = regexp_substr(&input, r"a*", 1, occurrence).unwrap();
+                let result_array = result.as_string::<i32>();
+                (!result_array.is_null(0)).then(|| result_array.value(0).to_string())
+            })
+            .collect();
+        assert_eq!(
+            substrings,
+            vec![
+                Some(String::new()),
+                Some("aaa".to_string()),
+                Some(String::new()),
+                None
+            ]
+        );
+
+        let result = regexp_match_offsets(&input, r"a*").unwrap();
+        let matches = result.as_list::<i32>().value(0);
+        let matches = matches.as_struct();
+        let start = matches.column_by_name("start").unwrap();
+        let end = matches.column_by_name("end").unwrap();
+        assert_eq!(start.as_ref(), &Int32Array::from(vec![0, 1, 4]));
+        assert_eq!(end.as_ref(), &Int32Array::from(vec![0, 4, 4]));
+    }
+
+    #[test]
+    fn test_regexp_instr_basic() {
+        let input = StringArray::from(vec![Some("abc123"), Some("123"), Some("abc"), None]);
+        let result = regexp_instr(&input, r"\d+").unwrap();
+        let result_array = result.as_any().downcast_ref::<Int32Array>().unwrap();
+        assert_eq!(result_array.value(0), 4);
+        assert_eq!(result_array.value(1), 1);
+        assert_eq!(result_array.value(2), 0);
+        assert!(result_array.is_null(3));
+    }
+
+    #[test]
+    fn test_regexp_instr_multibyte() {
+        // "é" and "ü" are two bytes each: the match is at byte offset 6 but character 5.
+        let input = StringArray::from(vec![Some("éaüb42")]);
+        let result = regexp_instr(&input, r"\d+").unwrap();
+        let result_array = result.as_any().downcast_ref::<Int32Array>().unwrap();
+        assert_eq!(result_array.value(0), 5);
+    }
+
+    #[test]
+    fn test_regexp_extract_named_basic() {
+        let input = StringArray::from(vec![Some("2024-01-15"), Some("no date"), None]);
+        let pattern = r"(?P<year>\d{4})-(?P<month>\d{2})-(?P<day>\d{2})";
+
+        let result = regexp_extract_named(&input, pattern, "month").unwrap();
+        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(result_array.value(0), "01");
+        assert_eq!(result_array.value(1), "");
+        assert!(result_array.is_null(2));
+    }
+
+    #[test]
+    fn test_regexp_extract_named_unknown_group() {
+        let input = StringArray::from(vec![Some("2024")]);
+        let err = regexp_extract_named(&input, r"(?P<year>\d{4})", "month").unwrap_err();
+        assert!(err.to_string().contains("month"));
+    }
+
+    #[test]
+    fn test_regexp_extract_flags_case_insensitive() {
+        let input = StringArray::from(vec![Some("HELLO123"), None]);
+        let result = regexp_extract_flags(&input, r"([a-z]+)(\d+)", 1, "i").unwrap();
+        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(result_array.value(0), "HELLO");
+        assert!(result_array.is_null(1));
+
+        // Without flags the pattern does not match.
+        let result = regexp_extract_flags(&input, r"([a-z]+)(\d+)", 1, "").unwrap();
+        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(result_array.value(0), "");
+    }
+
+    #[test]
+    fn test_regexp_extract_flags_multiline_dotall() {
+        let input = StringArray::from(vec![Some("first\nsecond")]);
+
+        let result = regexp_extract_flags(&input, r"^(\w+)$", 1, "m").unwrap();
+        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(result_array.value(0), "first");
+
+        let result = regexp_extract_flags(&input, r"first(.)second", 1, "s").unwrap();
+        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(result_array.value(0), "\n");
+    }
+
+    #[test]
+    fn test_regexp_extract_flags_verbose() {
+        let input = StringArray::from(vec![Some("order 2024-01-15 #42"), Some("no date")]);
+        let pattern = r"
+            (?P<year>\d{4})  # year
+            -
+            (?P<month>\d{2}) # month
+            -
+            (?P<day>\d{2})   # day
+            \ \#            # escaped space and hash
+            (\d+)
+        ";
+
+        let result = regexp_extract_flags(&input, pattern, 2, "x").unwrap();
+        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(result_array.value(0), "01");
+        assert_eq!(result_array.value(1), "");
+
+        let result = regexp_extract_flags(&input, pattern, 4, "x").unwrap();
+        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(result_array.value(0), "42");
+
+        // Without the flag the whitespace and comments are part of the pattern.
+        let result = regexp_extract_flags(&input, pattern, 2, "").unwrap();
+        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(result_array.value(0), "");
+    }
+
+    #[test]
+    #[cfg(not(feature = "fancy-regex"))]
+    fn test_regexp_extract_flags_crlf() {
+        let input = StringArray::from(vec![Some("first\r\nsecond\r\n")]);
+
+        // Without CRLF mode `$` only matches before `\n`, which `\w+` cannot reach past `\r`
+        let result = regexp_extract_flags(&input, r"^(\w+)$", 1, "m").unwrap();
+        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(result_array.value(0), "");
+
+        let result = regexp_extract_flags(&input, r"^(\w+)$", 1, "mR").unwrap();
+        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(result_array.value(0), "first");
+
+        let result = regexp_matches(&input, r"^(\w+)$", "gmR").unwrap();
+        let lines = result.as_list::<i32>().value(0);
+        let lines: Vec<_> = lines
+            .as_list::<i32>()
+            .iter()
+            .map(|groups| groups.unwrap().as_string::<i32>().value(0).to_string())
+            .collect();
+        assert_eq!(lines, vec!["first", "second"]);
+    }
+
+    #[test]
+    #[cfg(feature = "fancy-regex")]
+    fn test_regexp_extract_flags_crlf_unsupported() {
+        let input = StringArray::from(vec![Some("first\r\nsecond")]);
+        let err = regexp_extract_flags(&input, r"^(\w+)$", 1, "mR").unwrap_err();
+        assert_eq!(
+            RegexpError::find(&err),
+            Some(&RegexpError::InvalidFlag {
+                function: "regexp_extract_flags".to_string(),
+                flag: 'R',
+            })
+        );
+        assert!(!err.to_string().contains("'R' and"), "{err}");
+    }
+
+    #[test]
+    fn test_regexp_extract_flags_invalid() {
+        let input = StringArray::from(vec![Some("abc")]);
+        let err = regexp_extract_flags(&input, r"(abc)", 1, "iq").unwrap_err();
+        assert!(err.to_string().contains("invalid regex flag 'q'"));
+    }
+
+    #[test]
+    fn test_regexp_extract_opt_null_on_no_match() {
+        let input = StringArray::from(vec![Some("abc"), Some("def"), None]);
+        let pattern = r"([a-z]+)(\d+)?x";
+
+        // Neither row contains an "x", so the pattern does not match.
+        let result = regexp_extract_opt(&input, pattern, 1, true).unwrap();
+        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+        assert!(result_array.is_null(0));
+        assert!(result_array.is_null(1));
+        assert!(result_array.is_null(2));
+
+        // Without the flag the Spark-compatible empty string is returned.
+        let result = regexp_extract_opt(&input, pattern, 1, false).unwrap();
+        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(result_array.value(0), "");
+        assert!(result_array.is_null(2));
+    }
+
+    #[test]
+    fn test_regexp_extract_empty_array() {
+        let input = StringArray::from(Vec::<&str>::new());
+        let result = regexp_extract(&input, r"([a-z]+)(\d+)", 1).unwrap();
+        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(result_array.len(), 0);
+
+        // The pattern is not compiled, so an invalid one is not reported either.
+        let result = regexp_extract(&input, r"([a-z]+", 1).unwrap();
+        assert_eq!(result.len(), 0);
+    }
+
+    #[test]
+    fn test_regexp_extract_preserves_null_positions() {
+        let input = StringArray::from(vec![
+            None,
+            Some("abc123"),
+            None,
+            Some(""),
+            None,
+            Some("xyz"),
+        ]);
+        // Patterns matching an empty string, nothing at all, or an optional group.
+        for pattern in ["", ".*", r"(\d+)?", "nomatch", r"([a-z]+)(\d+)"] {
+            let result = regexp_extract(&input, pattern, 0).unwrap();
+            assert_eq!(result.nulls(), input.nulls(), "pattern {pattern:?}");
+            assert_eq!(result.null_count(), 3);
+            for i in [0, 2, 4] {
+                assert!(result.is_null(i), "row {i} for pattern {pattern:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_regexp_extract_with_config_no_match_behaviors() {
+        let input = StringArray::from(vec![Some("abc123"), Some("def"), None]);
+        let pattern = r"([a-z]+)(\d+)";
+        let extract = |no_match| {
+            let config = RegexpExtractConfig {
+                group_index: 2,
+                no_match,
+                ..Default::default()
+            };
+            regexp_extract_with_config(&input, pattern, &config)
+        };
+
+        let result = extract(NoMatchBehavior::EmptyString).unwrap();
+        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(result_array.value(0), "123");
+        assert_eq!(result_array.value(1), "");
+        assert!(result_array.is_null(2));
+
+        let result = extract(NoMatchBehavior::Null).unwrap();
+        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(result_array.value(0), "123");
+        assert!(result_array.is_null(1));
+        assert!(result_array.is_null(2));
+
+        let result = extract(NoMatchBehavior::Default("none".to_string())).unwrap();
+        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(result_array.value(1), "none");
+        assert!(result_array.is_null(2));
+
+        let err = extract(NoMatchBehavior::Error).unwrap_err();
+        assert!(err.to_string().contains("did not match a row"), "{err}");
+        assert!(!err.to_string().contains("def"), "{err}");
+    }
+
+    #[test]
+    fn test_regexp_extract_opt_empty_optional_group() {
+        let input = StringArray::from(vec![Some("abc"), Some("123")]);
+        let pattern = r"([a-z]+)(\d+)?";
+
+        // "abc" matches but group 2 does not participate: empty string, not null.
+        let result = regexp_extract_opt(&input, pattern, 2, true).unwrap();
+        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+        assert!(!result_array.is_null(0));
+        assert_eq!(result_array.value(0), "");
+        // "123" has no letters so the pattern does not match at all.
+        assert!(result_array.is_null(1));
+    }
+
+    #[test]
+    fn test_regexp_extract_large() {
+        let input = LargeStringArray::from(vec![Some("hello123"), Some("abc"), None]);
+        let result = regexp_extract_large(&input, r"([a-z]+)(\d+)", 1).unwrap();
+        let result_array = result.as_any().downcast_ref::<LargeStringArray>().unwrap();
+        assert_eq!(result_array.value(0), "hello");
+        assert_eq!(result_array.value(1), "");
+        assert!(result_array.is_null(2));
+    }
+
+    #[test]
+    fn test_create_regexp_extract_large_input() {
+        let input: ArrayRef = Arc::new(LargeStringArray::from(vec![Some("world456"), None]));
+        let args = [
+            ColumnarValue::Array(input),
+            ColumnarValue::Scalar(ScalarValue::Utf8(Some(r"([a-z]+)(\d+)".to_string()))),
+            ColumnarValue::Scalar(ScalarValue::UInt32(Some(2))),
+        ];
+        let result = match create_regexp_extract().invoke_batch(&args, 2).unwrap() {
+            ColumnarValue::Array(arr) => arr,
+            ColumnarValue::Scalar(_) => panic!("Expected array result"),
+        };
+        let result_array = result.as_any().downcast_ref::<LargeStringArray>().unwrap();
+        assert_eq!(result_array.value(0), "456");
+        assert!(result_array.is_null(1));
+    }
+
+    #[test]
+    fn test_regexp_extract_view() {
+        // Strings of up to 12 bytes are stored inline, longer ones in a data buffer.
+        let input = StringViewArray::from(vec![
+            Some("ab12"),
+            None,
+            Some("a much longer string value 789"),
+            Some("no digits in this long string"),
+        ]);
+        let result = regexp_extract_view(&input, r"([a-z]+) ?(\d+)", 2).unwrap();
+        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(result_array.value(0), "12");
+        assert!(result_array.is_null(1));
+        assert_eq!(result_array.value(2), "789");
+        assert_eq!(result_array.value(3), "");
+    }
+
+    #[test]
+    fn test_create_regexp_extract_view_input() {
+        let input: ArrayRef = Arc::new(StringViewArray::from(vec![
+            Some("hello123"),
+            Some("a considerably longer world456"),
+            None,
+        ]));
+        let args = [
+            ColumnarValue::Array(input),
+            ColumnarValue::Scalar(ScalarValue::Utf8(Some(r"([a-z]+)(\d+)".to_string()))),
+            ColumnarValue::Scalar(ScalarValue::UInt32(Some(1))),
+        ];
+        let result = match create_regexp_extract().invoke_batch(&args, 3).unwrap() {
+            ColumnarValue::Array(arr) => arr,
+            ColumnarValue::Scalar(_) => panic!("Expected array result"),
+        };
+        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(result_array.value(0), "hello");
+        assert_eq!(result_array.value(1), "world");
+        assert!(result_array.is_null(2));
+    }
+
+    #[test]
+    fn test_regexp_extract_dictionary() {
+        let values = ["user=alice", "user=bob", "anonymous"];
+        let rows: Vec<Option<&str>> = (0..300)
+            .map(|i| {
+                if i % 100 == 99 {
+                    None
+                } else {
+                    Some(values[i % 3])
+                }
+            })
+            .collect();
+        let input: DictionaryArray<Int32Type> = rows.iter().copied().collect();
+        assert_eq!(input.values().len(), 3);
+
+        let result = regexp_extract_dictionary(&input, r"user=(\w+)", 1).unwrap();
+        let result_array = result
+            .as_any()
+            .downcast_ref::<DictionaryArray<Int32Type>>()
+            .unwrap();
+
+        // Only the three distinct values were processed, keys are unchanged.
+        assert_eq!(result_array.values().len(), 3);
+        assert_eq!(result_array.keys(), input.keys());
+
+        let typed = result_array.downcast_dict::<StringArray>().unwrap();
+        for (i, row) in rows.iter().enumerate() {
+            match row {
+                None => assert!(result_array.is_null(i)),
+                Some(v) => {
+                    let expected = v.strip_prefix("user=").unwrap_or("");
+                    assert_eq!(typed.value(i), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_create_regexp_extract_dictionary_input() {
+        let input: DictionaryArray<Int8Type> = vec![Some("a1"), Some("b2"), None, Some("a1")]
+            .into_iter()
+            .collect();
+        let args = [
+            ColumnarValue::Array(Arc::new(input)),
+            ColumnarValue::Scalar(ScalarValue::Utf8(Some(r"[a-z](\d)".to_string()))),
+            ColumnarValue::Scalar(ScalarValue::UInt32(Some(1))),
+        ];
+        let result = match create_regexp_extract().invoke_batch(&args, 4).unwrap() {
+            ColumnarValue::Array(arr) => arr,
+            ColumnarValue::Scalar(_) => panic!("Expected array result"),
+        };
+        assert_eq!(
+            result.data_type(),
+            &DataType::Dictionary(Box::new(DataType::Int8), Box::new(DataType::Utf8))
+        );
+        let result_array = result
+            .as_any()
+            .downcast_ref::<DictionaryArray<Int8Type>>()
+            .unwrap();
+        let typed = result_array.downcast_dict::<StringArray>().unwrap();
+        assert_eq!(typed.value(0), "1");
+        assert_eq!(typed.value(1), "2");
+        assert!(result_array.is_null(2));
+        assert_eq!(typed.value(3), "1");
+    }
+
+    #[test]
+    fn test_create_regexp_extract_pattern_change() {
+        // The same UDF instance must not reuse a cached regex for a different pattern.
+        let udf = create_regexp_extract();
+        let input: ArrayRef = Arc::new(StringArray::from(vec![Some("abc123")]));
+        let mut results = vec![];
+        for pattern in [r"([a-z]+)", r"([a-z]+)", r"(\d+)"] {
+            let args = [
+                ColumnarValue::Array(Arc::clone(&input)),
+                ColumnarValue::Scalar(ScalarValue::Utf8(Some(pattern.to_string()))),
+                ColumnarValue::Scalar(ScalarValue::UInt32(Some(1))),
+            ];
+            let result = match udf.invoke_batch(&args, 1).unwrap() {
+                ColumnarValue::Array(arr) => arr,
+                ColumnarValue::Scalar(_) => panic!("Expected array result"),
+            };
+            let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+            results.push(result_array.value(0).to_string());
+        }
+        assert_eq!(results, vec!["abc", "abc", "123"]);
+    }
+
+    #[test]
+    fn test_create_regexp_extract_return_type() {
+        let udf = create_regexp_extract();
+        let return_type = |input_type: DataType| {
+            udf.return_type(&[input_type, DataType::Utf8, DataType::UInt32])
+                .unwrap()
+        };
+        assert_eq!(return_type(DataType::Utf8), DataType::Utf8);
+        assert_eq!(return_type(DataType::LargeUtf8), DataType::LargeUtf8);
+        assert_eq!(return_type(DataType::Utf8View), DataType::Utf8);
+        assert_eq!(
+            return_type(DataType::Dictionary(
+                Box::new(DataType::Int32),
+                Box::new(DataType::LargeUtf8)
+            )),
+            DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::LargeUtf8))
+        );
+    }
+
+    #[test]
+    fn test_create_regexp_extract_integer_group_index() {
+        let udf = create_regexp_extract();
+        let input: ArrayRef = Arc::new(StringArray::from(vec![Some("hello123")]));
+        for group_index in [
+            ScalarValue::Int8(Some(2)),
+            ScalarValue::Int16(Some(2)),
+            ScalarValue::Int32(Some(2)),
+            ScalarValue::Int64(Some(2)),
+            ScalarValue::UInt8(Some(2)),
+            ScalarValue::UInt16(Some(2)),
+            ScalarValue::UInt32(Some(2)),
+            ScalarValue::UInt64(Some(2)),
+        ] {
+            let args = [
+                ColumnarValue::Array(Arc::clone(&input)),
+                ColumnarValue::Scalar(ScalarValue::Utf8(Some(r"([a-z]+)(\d+)".to_string()))),
+                ColumnarValue::Scalar(group_index),
+            ];
+            let result = match udf.invoke_batch(&args, 1).unwrap() {
+                ColumnarValue::Array(arr) => arr,
+                ColumnarValue::Scalar(_) => panic!("Expected array result"),
+            };
+            let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+            assert_eq!(result_array.value(0), "123");
+        }
+    }
+
+    #[test]
+    fn test_create_regexp_extract_negative_group_index() {
+        let args = [
+            ColumnarValue::Array(Arc::new(StringArray::from(vec![Some("hello123")]))),
+            ColumnarValue::Scalar(ScalarValue::Utf8(Some(r"([a-z]+)".to_string()))),
+            ColumnarValue::Scalar(ScalarValue::Int64(Some(-1))),
+        ];
+        let err = create_regexp_extract().invoke_batch(&args, 1).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("group_index must be non-negative, got -1"));
+
+        let args = [
+            ColumnarValue::Array(Arc::new(StringArray::from(vec![Some("hello123")]))),
+            ColumnarValue::Scalar(ScalarValue::Utf8(Some(r"([a-z]+)".to_string()))),
+            ColumnarValue::Scalar(ScalarValue::Int16(Some(-3))),
+        ];
+        let err = create_regexp_extract().invoke_batch(&args, 1).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("group_index must be non-negative, got -3"));
+    }
+
+    #[test]
+    fn test_create_regexp_extract_invalid_pattern_error() {
+        let args = [
+            ColumnarValue::Array(Arc::new(StringArray::from(vec![Some("hello123")]))),
+            ColumnarValue::Scalar(ScalarValue::Utf8(Some(r"([a-z]+".to_string()))),
+            ColumnarValue::Scalar(ScalarValue::Int64(Some(1))),
+        ];
+        let err = create_regexp_extract().invoke_batch(&args, 1).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains(r#"regexp_extract: invalid pattern "([a-z]+": "#));
+
+        // Long patterns are cut off so the message stays readable.
+        let long_pattern = format!("({}", "a".repeat(200));
+        let input = StringArray::from(vec![Some("aaa")]);
+        let err = regexp_like(&input, &long_pattern).unwrap_err().to_string();
+        assert!(err.contains(&format!(
+            r#"regexp_like: invalid pattern "({}...""#,
+            "a".repeat(119)
+        )));
+    }
+
+    #[test]
+    fn test_create_regexp_extract_pattern_column() {
+        let args = [
+            ColumnarValue::Array(Arc::new(StringArray::from(vec![
+                Some("abc123"),
+                Some("abc123"),
+                Some("abc123"),
+                Some("abc123"),
+                None,
+            ]))),
+            ColumnarValue::Array(Arc::new(StringArray::from(vec![
+                Some(r"([a-z]+)"),
+                Some(r"(\d+)"),
+                Some(r"([a-z]+)"),
+                None,
+                Some(r"(\d+)"),
+            ]))),
+            ColumnarValue::Scalar(ScalarValue::Int64(Some(1))),
+        ];
+        let result = match create_regexp_extract().invoke_batch(&args, 5).unwrap() {
+            ColumnarValue::Array(arr) => arr,
+            ColumnarValue::Scalar(_) => panic!("Expected array result"),
+        };
+        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(result_array.value(0), "abc");
+        assert_eq!(result_array.value(1), "123");
+        assert_eq!(result_array.value(2), "abc");
+        // A null pattern or a null input yields a null row.
+        assert!(result_array.is_null(3));
+        assert!(result_array.is_null(4));
+    }
+
+    #[test]
+    fn test_create_regexp_extract_pattern_column_invalid() {
+        let args = [
+            ColumnarValue::Array(Arc::new(StringArray::from(vec![Some("abc"), Some("abc")]))),
+            ColumnarValue::Array(Arc::new(StringArray::from(vec![Some("(a)"), Some("(")]))),
+            ColumnarValue::Scalar(ScalarValue::Int64(Some(1))),
+        ];
+        assert!(create_regexp_extract().invoke_batch(&args, 2).is_err());
+    }
+
+    #[test]
+    fn test_regexp_extract_struct() {
+        let input = StringArray::from(vec![Some("abc123"), Some("def"), Some("!!"), None]);
+        let result = regexp_extract_struct(&input, r"([a-z]+)(?P<digits>\d+)?").unwrap();
+        let result_array = result.as_any().downcast_ref::<StructArray>().unwrap();
+        assert_eq!(result_array.num_columns(), 2);
+        assert_eq!(result_array.column_names(), vec!["g1", "digits"]);
+
+        let letters = result_array.column(0);
+        let letters = letters.as_any().downcast_ref::<StringArray>().unwrap();
+        let digits = result_array.column(1);
+        let digits = digits.as_any().downcast_ref::<StringArray>().unwrap();
+
+        assert_eq!((letters.value(0), digits.value(0)), ("abc", "123"));
+        // Optional group did not participate.
+        assert_eq!((letters.value(1), digits.value(1)), ("def", ""));
+        // No match at all.
+        assert!(!result_array.is_null(2));
+        assert_eq!((letters.value(2), digits.value(2)), ("", ""));
+        assert!(result_array.is_null(3));
+    }
+
+    #[test]
+    fn test_group_fields_do_not_collide_with_named_groups() {
+        let input = StringArray::from(vec!["ab"]);
+        let result = regexp_extract_struct(&input, r"(?P<g2>a)(b)").unwrap();
+        let result_array = result.as_struct();
+        assert_eq!(result_array.column_names(), vec!["g2", "g2_"]);
+        assert_eq!(
+            result_array
+                .column_by_name("g2_")
+                .unwrap()
+                .as_string::<i32>(),
+            &StringArray::from(vec!["b"])
+        );
+
+        let result = regexp_extract_all_groups(&input, r"(?P<g2>a)(b)").unwrap();
+        let DataType::List(field) = result.data_type() else {
+            panic!("Expected a list of structs");
+        };
+        let DataType::Struct(fields) = field.data_type() else {
+            panic!("Expected a list of structs");
+        };
+        let names: Vec<&str> = fields.iter().map(|field| field.name().as_str()).collect();
+        assert_eq!(names, vec!["g2", "g2_"]);
+    }
+
+    #[test]
+    fn test_regexp_extract_struct_no_groups() {
+        let input = StringArray::from(vec![Some("abc"), None]);
+        let result = regexp_extract_struct(&input, r"[a-z]+").unwrap();
+        let result_array = result.as_any().downcast_ref::<StructArray>().unwrap();
+        assert_eq!(result_array.num_columns(), 0);
+        assert_eq!(result_array.len(), 2);
+        assert!(result_array.is_null(1));
+    }
+
+    #[test]
+    fn test_regexp_extract_strict() {
+        let input = StringArray::from(vec![Some("hello123"), None]);
+        let result = regexp_extract_strict(&input, r"([a-z]+)(\d+)", 2).unwrap();
+        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(result_array.value(0), "123");
+        assert!(result_array.is_null(1));
+
+        // Group 0 is always valid, even without explicit groups.
+        assert!(regexp_extract_strict(&input, r"\d+", 0).is_ok());
+    }
+
+    #[test]
+    fn test_regexp_extract_strict_out_of_range() {
+        let input = StringArray::from(vec![Some("hello123")]);
+        let err = regexp_extract_strict(&input, r"([a-z]+)(\d+)", 99).unwrap_err();
+        assert!(err.to_string().contains("max valid index is 2"));
+
+        // The default variant stays Spark-compatible and returns an empty string.
+        let result = regexp_extract(&input, r"([a-z]+)(\d+)", 99).unwrap();
+        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(result_array.value(0), "");
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_regexp_extract_parallel() {
+        let rows: Vec<Option<String>> = (0..200_000)
+            .map(|i| (i % 7 != 0).then(|| format!("row{i}")))
+            .collect();
+        let input = StringArray::from(rows.clone());
+        let result = regexp_extract(&input, r"row(\d+)", 1).unwrap();
+        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(result_array.len(), rows.len());
+        for (i, row) in rows.iter().enumerate() {
+            match row {
+                Some(_) => assert_eq!(result_array.value(i), i.to_string()),
+                None => assert!(result_array.is_null(i)),
+            }
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_extract_parallel_preserves_order_across_chunks() {
+        use super::{compile_extract_regex, extract_parallel, extract_with_regex};
+
+        // Null, matching and non-matching rows cycle with a period of 3, which does not
+        // divide the power of two chunk sizes, so the chunk boundaries fall on every kind
+        // of row
+        let rows: Vec<Option<String>> = (0..3 * super::PARALLEL_THRESHOLD + 5)
+            .map(|i| match i % 3 {
+                0 => None,
+                1 => Some(format!("id={i}")),
+                _ => Some(format!("miss{i}")),
+            })
+            .collect();
+        let input = StringArray::from(rows.clone());
+        let re = compile_extract_regex("regexp_extract", r"id=(\d+)", "").unwrap();
+        let config = RegexpExtractConfig::new(1);
+
+        // A sliced input starts at a non-zero offset into its value and null buffers
+        for offset in [0, 1] {
+            let input = input.slice(offset, input.len() - 2 * offset);
+            let result = extract_parallel(&input, &re, &config).unwrap();
+            assert_eq!(result, extract_with_regex(&input, &re, &config).unwrap());
+
+            assert_eq!(result.len(), input.len());
+            for (i, row) in rows[offset..offset + input.len()].iter().enumerate() {
+                match row {
+                    None => assert!(result.is_null(i), "row {i}"),
+                    Some(row) => assert_eq!(
+                        result.value(i),
+                        row.strip_prefix("id=").unwrap_or(""),
+                        "row {i}"
+                    ),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_create_regexp_extract_scalar_input() {
+        let udf = create_regexp_extract();
+        let invoke = |input: ScalarValue| {
+            let args = [
+                ColumnarValue::Scalar(input),
+                ColumnarValue::Scalar(ScalarValue::Utf8(Some(r"([a-z]+)(\d+)".to_string()))),
+                ColumnarValue::Scalar(ScalarValue::Int64(Some(2))),
+            ];
+            match udf.invoke_batch(&args, 1).unwrap() {
+                ColumnarValue::Scalar(scalar) => scalar,
+                ColumnarValue::Array(_) => panic!("Expected scalar result"),
+            }
+        };
+
+        assert_eq!(
+            invoke(ScalarValue::Utf8(Some("abc123".to_string()))),
+            ScalarValue::Utf8(Some("123".to_string()))
+        );
+        assert_eq!(invoke(ScalarValue::Utf8(None)), ScalarValue::Utf8(None));
+        assert_eq!(
+            invoke(ScalarValue::LargeUtf8(Some("abc123".to_string()))),
+            ScalarValue::LargeUtf8(Some("123".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_regexp_split() {
+        let input = StringArray::from(vec![Some("a,b,,c,"), Some(",x"), Some(""), None]);
+        let result = regexp_split(&input, ",").unwrap();
+        let result_array = result.as_any().downcast_ref::<ListArray>().unwrap();
+
+        let pieces = |i: usize| -> Vec<String> {
+            let values = result_array.value(i);
+            let values = values.as_any().downcast_ref::<StringArray>().unwrap();
+            values.iter().map(|v| v.unwrap().to_string()).collect()
+        };
+        assert_eq!(pieces(0), ["a", "b", "", "c", ""]);
+        assert_eq!(pieces(1), ["", "x"]);
+        assert_eq!(pieces(2), [""]);
+        assert!(result_array.is_null(3));
+    }
+
+    #[test]
+    fn test_regexp_split_limit() {
+        let input = StringArray::from(vec![Some("a1b22c3"), None]);
+        let pieces = |limit: i32| -> Vec<String> {
+            let result = regexp_split_limit(&input, r"\d+", limit).unwrap();
+            let result_array = result.as_any().downcast_ref::<ListArray>().unwrap();
+            assert!(result_array.is_null(1));
+            let values = result_array.value(0);
+            let values = values.as_any().downcast_ref::<StringArray>().unwrap();
+            values.iter().map(|v| v.unwrap().to_string()).collect()
+        };
+
+        // A limit of 1 leaves the whole string as the only piece.
+        assert_eq!(pieces(1), ["a1b22c3"]);
+        assert_eq!(pieces(2), ["a", "b22c3"]);
+        // Non-positive limits split everywhere and keep the trailing empty piece.
+        assert_eq!(pieces(-1), ["a", "b", "c", ""]);
+        assert_eq!(pieces(0), ["a", "b", "c", ""]);
+    }
+
+    #[test]
+    fn test_lru_regex_cache_evicts_least_recently_used() {
+        let mut cache = LruRegexCache::new(2);
+        let re = Arc::new(Regex::new("a").unwrap());
+        cache.insert("a".to_string(), Arc::clone(&re));
+        cache.insert("b".to_string(), Arc::clone(&re));
+        // Using "a" makes "b" the least recently used entry.
+        assert!(cache.get("a").is_some());
+        cache.insert("c".to_string(), Arc::clone(&re));
+        assert!(cache.get("a").is_some());
+        assert!(cache.get("b").is_none());
+        assert!(cache.get("c").is_some());
+
+        cache.set_capacity(0);
+        assert!(cache.get("a").is_none());
+        assert!(cache.get("c").is_none());
+    }
+
+    #[test]
+    fn test_compile_regex_shares_compiled_patterns() {
+        let first = compile_regex("regexp_like", r"shared-(\d+)").unwrap();
+        let second = compile_regex("regexp_count", r"shared-(\d+)").unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+        // Flags are part of the key.
+        let with_flags = build_regex(r"shared-(\d+)", "i").unwrap();
+        let without_flags = build_regex(r"shared-(\d+)", "").unwrap();
+        assert!(!Arc::ptr_eq(&without_flags, &with_flags));
+    }
+
+    #[test]
+    fn test_validate_pattern_matches_udf_error() {
+        let input = StringArray::from(vec![Some("abc")]);
+        let udf_err = regexp_extract(&input, r"(\d", 1).unwrap_err();
+        let err = validate_pattern(r"(\d").unwrap_err();
+        assert_eq!(err.to_string(), udf_err.to_string());
+        assert!(validate_pattern(r"(\d)").is_ok());
+    }
+
+    #[test]
+    fn test_regex_unicode_mode() {
+        let input = "héllo wörld";
+        let ascii = RegexpOptions {
+            unicode: false,
+            ..Default::default()
+        };
+
+        let re = regex_builder(r"\w+", &RegexpOptions::default())
+            .build()
+            .unwrap();
+        assert_eq!(re.find(input).unwrap().as_str(), "héllo");
+
+        // Without Unicode mode `\w` stops at the first multi-byte character
+        let re = regex_builder(r"\w+", &ascii).build().unwrap();
+        assert_eq!(re.find(input).unwrap().as_str(), "h");
+
+        // `.` could match a single byte of a multi-byte character
+        assert!(regex_builder("h.llo", &RegexpOptions::default())
+            .build()
+            .is_ok());
+        assert!(regex_builder("h.llo", &ascii).build().is_err());
+    }
+
+    #[test]
+    fn test_regex_size_limit_exceeded() {
+        let options = RegexpOptions {
+            size_limit: 1_000,
+            ..Default::default()
+        };
+        let pattern = "a{1000}";
+        assert!(regex_builder(pattern, &RegexpOptions::default())
+            .build()
+            .is_ok());
+
+        let error = regex_builder(pattern, &options).build().unwrap_err();
+        let err = invalid_pattern("regexp_extract", pattern, error).to_string();
+        assert!(
+            err.contains(r#"regexp_extract: invalid pattern "a{1000}""#),
+            "{err}"
+        );
+        assert!(err.contains("size limit"), "{err}");
+    }
+
+    #[test]
+    fn test_regexp_extract_generic_offset_sizes() {
+        let values = vec![Some("hello123"), Some("nodigits"), None];
+        let small =
+            regexp_extract_generic(&StringArray::from(values.clone()), r"(\d+)", 1).unwrap();
+        let large = regexp_extract_generic(&LargeStringArray::from(values), r"(\d+)", 1).unwrap();
+        assert_eq!(small, StringArray::from(vec![Some("123"), Some(""), None]));
+        assert_eq!(
+            large,
+            LargeStringArray::from(vec![Some("123"), Some(""), None])
+        );
+    }
+
+    #[test]
+    fn test_regexp_match_offsets_multibyte() {
+        // "é" and "ü" take two bytes each, the offsets must still count characters.
+        let input = StringArray::from(vec![Some("café 12 über 345"), Some("none"), None]);
+        let result = regexp_match_offsets(&input, r"\d+").unwrap();
+        let result_array = result.as_any().downcast_ref::<ListArray>().unwrap();
+
+        let matches = result_array.value(0);
+        let matches = matches.as_any().downcast_ref::<StructArray>().unwrap();
+        let start = matches.column_by_name("start").unwrap();
+        let end = matches.column_by_name("end").unwrap();
+        assert_eq!(start.as_ref(), &Int32Array::from(vec![5, 13]));
+        assert_eq!(end.as_ref(), &Int32Array::from(vec![7, 16]));
+        let chars: Vec<char> = "café 12 über 345".chars().collect();
+        assert_eq!(chars[13..16].iter().collect::<String>(), "345");
+
+        assert_eq!(result_array.value(1).len(), 0);
+        assert!(result_array.is_null(2));
+    }
+
+    #[test]
+    fn test_zero_width_matches_terminate() {
+        let input = StringArray::from(vec![Some("aaa")]);
+        let list_values = |result: ArrayRef| -> Vec<String> {
+            let result_array = result.as_any().downcast_ref::<ListArray>().unwrap();
+            let values = result_array.value(0);
+            let values = values.as_any().downcast_ref::<StringArray>().unwrap();
+            values.iter().map(|v| v.unwrap().to_string()).collect()
+        };
+        let count = |pattern| {
+            let result = regexp_count(&input, pattern).unwrap();
+            result
+                .as_any()
+                .downcast_ref::<Int64Array>()
+                .unwrap()
+                .value(0)
+        };
+
+        // An empty pattern matches before every character and at the end.
+        assert_eq!(
+            list_values(regexp_extract_all(&input, "", 0).unwrap()),
+            ["", "", "", ""]
+        );
+        assert_eq!(count(""), 4);
+        assert_eq!(
+            list_values(regexp_split(&input, "").unwrap()),
+            ["", "a", "a", "a", ""]
+        );
+
+        // `(a*)` matches "aaa", then the empty string at the end, which directly follows
+        // "aaa" and is reported like Spark does.
+        assert_eq!(
+            list_values(regexp_extract_all(&input, "(a*)", 1).unwrap()),
+            ["aaa", ""]
+        );
+        assert_eq!(count("(a*)"), 2);
+        let groups = regexp_extract_all_groups(&input, "(a*)").unwrap();
+        assert_eq!(groups.as_list::<i32>().value_length(0), 2);
+        let matches = regexp_matches(&input, "(a*)", "g").unwrap();
+        assert_eq!(matches.as_list::<i32>().value_length(0), 2);
+        assert_eq!(list_values(regexp_split(&input, "a*").unwrap()), ["", ""]);
+    }
+
+    #[test]
+    fn test_group_count() {
+        assert_eq!(group_count(r"(?P<y>\d{4})-(\d{2})-(?:\d{2})").unwrap(), 2);
+        assert_eq!(group_count("").unwrap(), 0);
+        let err = group_count(r"(\d").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            validate_pattern(r"(\d").unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn test_named_groups() {
+        let names = named_groups(r"(?P<b>x)(y)(?<a>z)").unwrap();
+        assert_eq!(names, ["b", "a"]);
+        assert!(named_groups(r"(\d+)").unwrap().is_empty());
+        assert!(named_groups(r"(?P<a>").is_err());
+    }
+
+    #[test]
+    fn test_create_regexp_extract_binary_invalid_utf8() {
+        let input: ArrayRef = Arc::new(BinaryArray::from(vec![
+            Some(&b"\xC3(key=\xFFval)"[..]),
+            Some(&b"no match"[..]),
+            None,
+        ]));
+        let args = [
+            ColumnarValue::Array(input),
+            ColumnarValue::Scalar(ScalarValue::Utf8(Some(r"(?-u)key=(.+)\)".to_string()))),
+            ColumnarValue::Scalar(ScalarValue::UInt32(Some(1))),
+        ];
+        let result = match create_regexp_extract_binary()
+            .invoke_batch(&args, 3)
+            .unwrap()
+        {
+            ColumnarValue::Array(arr) => arr,
+            ColumnarValue::Scalar(_) => panic!("Expected array result"),
+        };
+        let result_array = result.as_any().downcast_ref::<BinaryArray>().unwrap();
+        assert_eq!(result_array.value(0), b"\xFFval");
+        assert_eq!(result_array.value(1), b"");
+        assert!(result_array.is_null(2));
+    }
+
+    #[test]
+    fn test_create_regexp_extract_list_input() {
+        let mut builder = ListBuilder::new(StringBuilder::new());
+        builder.append_value([Some("a1"), None, Some("b22")]);
+        builder.append_value(Vec::<Option<&str>>::new());
+        builder.append_null();
+        builder.append_value([Some("none")]);
+        let input: ArrayRef = Arc::new(builder.finish());
+
+        let args = [
+            ColumnarValue::Array(Arc::clone(&input)),
+            ColumnarValue::Scalar(ScalarValue::Utf8(Some(r"[a-z](\d+)".to_string()))),
+            ColumnarValue::Scalar(ScalarValue::Int64(Some(1))),
+        ];
+        let udf = create_regexp_extract();
+        let return_type = udf.return_type(&[input.data_type().clone()]).unwrap();
+        let result = match udf.invoke_batch(&args, 4).unwrap() {
+            ColumnarValue::Array(arr) => arr,
+            ColumnarValue::Scalar(_) => panic!("Expected array result"),
+        };
+        assert_eq!(result.data_type(), &return_type);
+
+        let result_array = result.as_any().downcast_ref::<ListArray>().unwrap();
+        let input_array = input.as_any().downcast_ref::<ListArray>().unwrap();
+        assert_eq!(result_array.value_offsets(), input_array.value_offsets());
+        assert_eq!(result_array.nulls(), input_array.nulls());
+
+        let values = result_array.values();
+        let values = values.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(
+            values,
+            &StringArray::from(vec![Some("1"), None, Some("22"), Some("")])
+        );
+        assert_eq!(result_array.value(1).len(), 0);
+        assert!(result_array.is_null(2));
+    }
+
+    #[test]
+    fn test_regexp_extract_default_no_match() {
+        let args = [
+            ColumnarValue::Array(Arc::new(StringArray::from(vec![
+                Some("id=42"),
+                Some("none"),
+                None,
+            ]))),
+            ColumnarValue::Scalar(ScalarValue::Utf8(Some(r"id=(\d+)".to_string()))),
+            ColumnarValue::Scalar(ScalarValue::UInt32(Some(1))),
+            ColumnarValue::Scalar(ScalarValue::Utf8(Some("UNKNOWN".to_string()))),
+        ];
+        let result = match create_regexp_extract_default()
+            .invoke_batch(&args, 3)
+            .unwrap()
+        {
+            ColumnarValue::Array(arr) => arr,
+            ColumnarValue::Scalar(_) => panic!("Expected array result"),
+        };
+        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(result_array.value(0), "42");
+        assert_eq!(result_array.value(1), "UNKNOWN");
+        assert!(result_array.is_null(2));
+    }
+
+    #[test]
+    fn test_regexp_extract_literal_pattern() {
+        let input = StringArray::from(vec![Some("version 1.2 or 1x2"), Some("1x2 only")]);
+        let config = RegexpExtractConfig {
+            literal: true,
+            ..Default::default()
+        };
+
+        // As a regex `.` matches any character, so "1x2" matches too
+        let result = regexp_extract(&input, "1.2", 0).unwrap();
+        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(result_array.value(1), "1x2");
+
+        let result = regexp_extract_with_config(&input, "1.2", &config).unwrap();
+        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(result_array.value(0), "1.2");
+        assert_eq!(result_array.value(1), "");
+
+        // Special characters that would not even compile as a regex are matched as well
+        let input = StringArray::from(vec!["cost: $(5+"]);
+        let result = regexp_extract_with_config(&input, "$(5+", &config).unwrap();
+        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(result_array.value(0), "$(5+");
+
+        let udf = create_regexp_extract_with(config);
+        let args = [
+            ColumnarValue::Array(Arc::new(input)),
+            ColumnarValue::Scalar(ScalarValue::Utf8(Some("(5".to_string()))),
+        ];
+        let result = match udf.invoke_batch(&args, 1).unwrap() {
+            ColumnarValue::Array(arr) => arr,
+            ColumnarValue::Scalar(_) => panic!("Expected array result"),
+        };
+        assert_eq!(result.as_string::<i32>().value(0), "(5");
+    }
+
+    #[test]
+    fn test_regexp_extract_default_missing_group() {
+        // "abc" matches, but the optional digits group does not participate.
+        let input = StringArray::from(vec![Some("abc"), Some("abc1"), Some("123")]);
+        let pattern = r"^([a-z]+)(\d+)?$";
+
+        let result = regexp_extract_default(&input, pattern, 2, "UNKNOWN").unwrap();
+        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(result_array.value(0), "");
+        assert_eq!(result_array.value(1), "1");
+        assert_eq!(result_array.value(2), "UNKNOWN");
+
+        let config = RegexpExtractConfig {
+            group_index: 2,
+            no_match: NoMatchBehavior::Default("UNKNOWN".to_string()),
+            missing_group_as_no_match: true,
+            ..Default::default()
+        };
+        let result = regexp_extract_with_config(&input, pattern, &config).unwrap();
+        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(result_array.value(0), "UNKNOWN");
+        assert_eq!(result_array.value(1), "1");
+        assert_eq!(result_array.value(2), "UNKNOWN");
+    }
+
+    #[test]
+    fn test_regexp_extract_anchored_prefix_match() {
+        let input = StringArray::from(vec![
+            Some("978-3-16-148410-0"),
+            Some("978-3-16-148410-0 trailing"),
+            Some("x978-3-16-148410-0"),
+            None,
+        ]);
+        let pattern = r"(\d{3})-\d-\d{2}-\d{6}-\d";
+        let result = regexp_extract_anchored(&input, pattern, 1).unwrap();
+        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(result_array.value(0), "978");
+        // The unanchored regexp_extract would match both of these.
+        assert_eq!(result_array.value(1), "");
+        assert_eq!(result_array.value(2), "");
+        assert!(result_array.is_null(3));
+
+        // An alternation applies to the whole pattern, not just the last branch.
+        let input = StringArray::from(vec!["ab", "abc", "b"]);
+        let result = regexp_extract_anchored(&input, "a|ab|b", 0).unwrap();
+        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(result_array, &StringArray::from(vec!["ab", "", "b"]));
+    }
+
+    #[cfg(feature = "fancy-regex")]
+    #[test]
+    fn test_regexp_extract_fancy_lookahead() {
+        let input = StringArray::from(vec![Some("foobar"), Some("foobaz"), None]);
+        let result = regexp_extract(&input, "foo(?=bar)", 0).unwrap();
         let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
-        assert_eq!(result_array.value(0), "100");
+        assert_eq!(
+            result_array,
+            &StringArray::from(vec![Some("foo"), Some(""), None])
+        );
     }
 
+    #[cfg(feature = "fancy-regex")]
     #[test]
-    fn test_regexp_extract_no_digits() {
-        let input = StringArray::from(vec![Some("foo")]);
-        let result = regexp_extract(&input, r"(\d+)", 1).unwrap();
+    fn test_regexp_extract_fancy_backreference() {
+        let input = StringArray::from(vec![Some("say hello hello"), Some("no repeat")]);
+        let result = regexp_extract(&input, r"(\w+) \1", 1).unwrap();
         let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
-        assert_eq!(result_array.value(0), "");
+        assert_eq!(
+            result_array,
+            &StringArray::from(vec![Some("hello"), Some("")])
+        );
+
+        let result = regexp_extract_flags(&input, r"(HELLO) \1", 1, "i").unwrap();
+        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(result_array.value(0), "hello");
+        assert_eq!(result_array.value(1), "");
     }
 
     #[test]
-    fn test_regexp_extract_missing_optional_group() {
-        let input = StringArray::from(vec![Some("aaaac")]);
-        let result = regexp_extract(&input, "(a+)(b)?(c)", 2).unwrap();
+    fn test_create_regexp_extract_group_index_column() {
+        let args = [
+            ColumnarValue::Array(Arc::new(StringArray::from(vec![
+                Some("hello123"),
+                Some("world456"),
+                Some("nodigits"),
+                Some("abc1"),
+                Some("abc1"),
+            ]))),
+            ColumnarValue::Scalar(ScalarValue::Utf8(Some(r"([a-z]+)(\d+)".to_string()))),
+            ColumnarValue::Array(Arc::new(Int64Array::from(vec![
+                Some(1),
+                Some(2),
+                Some(1),
+                Some(3),
+                None,
+            ]))),
+        ];
+        let result = match create_regexp_extract().invoke_batch(&args, 5).unwrap() {
+            ColumnarValue::Array(arr) => arr,
+            ColumnarValue::Scalar(_) => panic!("Expected array result"),
+        };
         let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
-        assert_eq!(result_array.value(0), "");
+        assert_eq!(result_array.value(0), "hello");
+        assert_eq!(result_array.value(1), "456");
+        assert_eq!(result_array.value(2), "");
+        // An out-of-range index yields an empty string, a null index a null row.
+        assert_eq!(result_array.value(3), "");
+        assert!(result_array.is_null(4));
+    }
+
+    #[test]
+    fn test_regexp_extract_all_groups() {
+        let input = StringArray::from(vec![Some("a=1 b=2"), Some("none"), None, Some("c=")]);
+        let result = regexp_extract_all_groups(&input, r"(\w)=(\d)?").unwrap();
+        let result_array = result.as_any().downcast_ref::<ListArray>().unwrap();
+
+        assert_eq!(result_array.value_offsets(), &[0, 2, 2, 2, 3]);
+        // No matches give an empty list, a null input a null list.
+        assert!(result_array.is_valid(1));
+        assert!(result_array.is_null(2));
+
+        let matches = result_array.values();
+        let matches = matches.as_any().downcast_ref::<StructArray>().unwrap();
+        let keys = matches.column_by_name("g1").unwrap();
+        let values = matches.column_by_name("g2").unwrap();
+        assert_eq!(keys.as_ref(), &StringArray::from(vec!["a", "b", "c"]));
+        assert_eq!(values.as_ref(), &StringArray::from(vec!["1", "2", ""]));
+    }
+
+    #[test]
+    fn test_regexp_extract_trim() {
+        let input = StringArray::from(vec![Some("key=  value  ;"), Some("key=;")]);
+        let mut config = RegexpExtractConfig::new(1);
+        let result = regexp_extract_with_config(&input, r"key=([^;]*);", &config).unwrap();
+        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(result_array.value(0), "  value  ");
+
+        config.trim = true;
+        let result = regexp_extract_with_config(&input, r"key=([^;]*);", &config).unwrap();
+        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(result_array.value(0), "value");
+        assert_eq!(result_array.value(1), "");
+    }
+
+    #[test]
+    fn test_regexp_extract_case_transform() {
+        let input = StringArray::from(vec![Some("id=AbC-1"), Some("none"), None]);
+        let mut config = RegexpExtractConfig {
+            group_index: 1,
+            no_match: NoMatchBehavior::Default("Unknown".to_string()),
+            case: CaseTransform::Lower,
+            ..Default::default()
+        };
+        let result = regexp_extract_with_config(&input, r"id=([\w-]+)", &config).unwrap();
+        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(result_array.value(0), "abc-1");
+        // The no-match value is returned as given.
+        assert_eq!(result_array.value(1), "Unknown");
+        assert!(result_array.is_null(2));
+
+        config.case = CaseTransform::Upper;
+        let result = regexp_extract_with_config(&input, r"id=([\w-]+)", &config).unwrap();
+        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(result_array.value(0), "ABC-1");
+        assert_eq!(result_array.value(1), "Unknown");
+    }
+
+    #[test]
+    fn test_regexp_extract_max_len() {
+        let input = StringArray::from(vec![
+            Some("name=Zoë Saldaña;"),
+            Some("name=Ada;"),
+            Some("name=日本語のテキスト;"),
+            Some("none"),
+        ]);
+        let mut config = RegexpExtractConfig {
+            group_index: 1,
+            no_match: NoMatchBehavior::Default("not available".to_string()),
+            max_len: Some(5),
+            ..Default::default()
+        };
+        let result = regexp_extract_with_config(&input, r"name=([^;]*);", &config).unwrap();
+        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+        // Five characters, not five bytes, so the multi-byte characters stay whole
+        assert_eq!(result_array.value(0), "Zoë S");
+        assert_eq!(result_array.value(1), "Ada");
+        assert_eq!(result_array.value(2), "日本語のテ");
+        // The no-match value is returned as given.
+        assert_eq!(result_array.value(3), "not available");
+
+        // Uppercasing "ß" yields two characters, the limit applies to the folded group
+        let input = StringArray::from(vec!["straße"]);
+        config.case = CaseTransform::Upper;
+        let result = regexp_extract_with_config(&input, r"(\w+)", &config).unwrap();
+        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(result_array.value(0), "STRAS");
+    }
+
+    #[test]
+    fn test_create_regexp_extract_null_pattern() {
+        let args = [
+            ColumnarValue::Array(Arc::new(StringArray::from(vec![
+                Some("hello123"),
+                None,
+                Some("world456"),
+            ]))),
+            ColumnarValue::Scalar(ScalarValue::Utf8(None)),
+            ColumnarValue::Scalar(ScalarValue::UInt32(Some(1))),
+        ];
+        let result = match create_regexp_extract().invoke_batch(&args, 3).unwrap() {
+            ColumnarValue::Array(arr) => arr,
+            ColumnarValue::Scalar(_) => panic!("Expected array result"),
+        };
+        assert_eq!(result.data_type(), &DataType::Utf8);
+        assert_eq!(result.len(), 3);
+        assert_eq!(result.null_count(), 3);
+    }
+
+    #[test]
+    fn test_create_regexp_extract_large_pattern() {
+        let udf = create_regexp_extract();
+        for pattern in [
+            ScalarValue::LargeUtf8(Some(r"([a-z]+)(\d+)".to_string())),
+            ScalarValue::Utf8View(Some(r"([a-z]+)(\d+)".to_string())),
+        ] {
+            let args = [
+                ColumnarValue::Array(Arc::new(StringArray::from(vec![Some("hello123"), None]))),
+                ColumnarValue::Scalar(pattern),
+                ColumnarValue::Scalar(ScalarValue::UInt32(Some(2))),
+            ];
+            let result = match udf.invoke_batch(&args, 2).unwrap() {
+                ColumnarValue::Array(arr) => arr,
+                ColumnarValue::Scalar(_) => panic!("Expected array result"),
+            };
+            let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+            assert_eq!(result_array.value(0), "123");
+            assert!(result_array.is_null(1));
+        }
+    }
+
+    #[test]
+    fn test_regexp_extract_run_end_encoded() {
+        let run_ends = Int32Array::from(vec![1000, 1500, 2500, 2600]);
+        let values = StringArray::from(vec![
+            Some("level=INFO"),
+            Some("level=WARN"),
+            None,
+            Some("INFO"),
+        ]);
+        let input = RunArray::try_new(&run_ends, &values).unwrap();
+        let invoke = |input: ArrayRef| {
+            let args = [
+                ColumnarValue::Array(input),
+                ColumnarValue::Scalar(ScalarValue::Utf8(Some(r"level=(\w+)".to_string()))),
+                ColumnarValue::Scalar(ScalarValue::Int64(Some(1))),
+            ];
+            match create_regexp_extract().invoke_batch(&args, 2600).unwrap() {
+                ColumnarValue::Array(arr) => arr,
+                ColumnarValue::Scalar(_) => panic!("Expected array result"),
+            }
+        };
+
+        let result = invoke(Arc::new(input.clone()));
+        let result_array = result
+            .as_any()
+            .downcast_ref::<RunArray<Int32Type>>()
+            .unwrap();
+        assert_eq!(result_array.run_ends().values(), &[1000, 1500, 2500, 2600]);
+        assert_eq!(
+            result_array.values().as_string::<i32>(),
+            &StringArray::from(vec![Some("INFO"), Some("WARN"), None, Some("")])
+        );
+
+        // A sliced input keeps its logical offset and length.
+        let result = invoke(Arc::new(input.slice(1200, 400)));
+        let result_array = result
+            .as_any()
+            .downcast_ref::<RunArray<Int32Type>>()
+            .unwrap();
+        assert_eq!(result_array.len(), 400);
+        let result_values = result_array.values().as_string::<i32>();
+        assert_eq!(
+            result_values.value(result_array.get_physical_index(0)),
+            "WARN"
+        );
+        assert_eq!(
+            result_values.value(result_array.get_physical_index(299)),
+            "WARN"
+        );
+        assert!(result_values.is_null(result_array.get_physical_index(300)));
+    }
+
+    #[test]
+    fn test_regexp_extract_str() {
+        let pattern = r"([a-z]+)(\d+)?-";
+        assert_eq!(
+            regexp_extract_str("hello123-", pattern, 1)
+                .unwrap()
+                .as_deref(),
+            Some("hello")
+        );
+        assert_eq!(regexp_extract_str("123", pattern, 1).unwrap(), None);
+        // A group that did not participate and an out-of-range group both yield "".
+        assert_eq!(
+            regexp_extract_str("hello-", pattern, 2).unwrap().as_deref(),
+            Some("")
+        );
+        assert_eq!(
+            regexp_extract_str("hello-", pattern, 5).unwrap().as_deref(),
+            Some("")
+        );
+        assert!(regexp_extract_str("hello", r"(", 1).is_err());
+
+        let config = RegexpExtractConfig::new(1);
+        assert_eq!(
+            regexp_extract_str_with_config("123", pattern, &config)
+                .unwrap()
+                .as_deref(),
+            Some("")
+        );
+    }
+
+    #[test]
+    fn test_regexp_substr() {
+        let input = StringArray::from(vec![Some("a1b22c333"), Some("é1é22"), Some("a1"), None]);
+        let result = regexp_substr(&input, r"\d+", 1, 2).unwrap();
+        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(result_array.value(0), "22");
+        assert_eq!(result_array.value(1), "22");
+        assert!(result_array.is_null(2));
+        assert!(result_array.is_null(3));
+
+        // Positions count characters, the search starts inside "22".
+        let result = regexp_substr(&input, r"\d+", 5, 1).unwrap();
+        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(result_array.value(0), "2");
+        assert_eq!(result_array.value(1), "2");
+
+        // A position beyond the end of the string yields null.
+        let result = regexp_substr(&input, r"\d*", 20, 1).unwrap();
+        let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+        assert!(result_array.is_null(0));
+        assert!(result_array.is_null(2));
+
+        assert!(regexp_substr(&input, r"\d+", 0, 1).is_err());
+        assert!(regexp_substr(&input, r"\d+", 1, 0).is_err());
+    }
+
+    #[test]
+    fn test_create_regexp_substr_null_and_out_of_range_arguments() {
+        let udf = create_regexp_substr();
+        let input = ColumnarValue::Array(Arc::new(StringArray::from(vec![Some("a1b22"), None])));
+        let pattern = ColumnarValue::Scalar(ScalarValue::Utf8(Some(r"\d+".to_string())));
+        let one = ColumnarValue::Scalar(ScalarValue::Int64(Some(1)));
+
+        for args in [
+            [
+                input.clone(),
+                ColumnarValue::Scalar(ScalarValue::Utf8(None)),
+                one.clone(),
+                one.clone(),
+            ],
+            [
+                input.clone(),
+                pattern.clone(),
+                ColumnarValue::Scalar(ScalarValue::Int64(None)),
+                one.clone(),
+            ],
+            [
+                input.clone(),
+                pattern.clone(),
+                one.clone(),
+                ColumnarValue::Scalar(ScalarValue::Int64(None)),
+            ],
+        ] {
+            let ColumnarValue::Array(result) = udf.invoke_batch(&args, 2).unwrap() else {
+                panic!("Expected array result");
+            };
+            assert_eq!(result.data_type(), &DataType::Utf8);
+            assert_eq!(result.null_count(), 2);
+        }
+
+        let too_large = ColumnarValue::Scalar(ScalarValue::Int64(Some(i64::from(i32::MAX) + 1)));
+        let err = udf
+            .invoke_batch(&[input, pattern, one, too_large], 2)
+            .unwrap_err();
+        assert!(matches!(
+            RegexpError::find(&err),
+            Some(RegexpError::UnsupportedInputType { function, actual: DataType::Int64, .. })
+                if function == "regexp_substr"
+        ));
+        assert!(err
+            .to_string()
+            .contains("regexp_substr expects occurrence within the Int32 range, got Int64. 2147483648 is out of range for Int32"));
+    }
+    #[test]
+    fn test_regexp_extract_view_output() {
+        let input = StringViewArray::from(vec![
+            Some("host=a-considerably-long-hostname.example.com"),
+            Some("host=short"),
+            Some("no host"),
+            None,
+        ]);
+        let result = regexp_extract_view_to_view(&input, r"host=([\w.-]+)", 1).unwrap();
+        let result_array = result.as_any().downcast_ref::<StringViewArray>().unwrap();
+        assert_eq!(
+            result_array.value(0),
+            "a-considerably-long-hostname.example.com"
+        );
+        assert_eq!(result_array.value(1), "short");
+        assert_eq!(result_array.value(2), "");
+        assert!(result_array.is_null(3));
+        // The long value references the input buffer rather than a copy.
+        assert_eq!(
+            result_array.data_buffers().len(),
+            input.data_buffers().len()
+        );
+        assert!(result_array.data_buffers()[0].ptr_eq(&input.data_buffers()[0]));
+
+        let udf = ScalarUDF::new_from_impl(RegexpExtract::new().with_view_output(true));
+        let arg_types = [DataType::Utf8View, DataType::Utf8, DataType::Int64];
+        assert_eq!(udf.return_type(&arg_types).unwrap(), DataType::Utf8View);
+        let args = [
+            ColumnarValue::Array(Arc::new(input)),
+            ColumnarValue::Scalar(ScalarValue::Utf8(Some(r"host=([\w.-]+)".to_string()))),
+            ColumnarValue::Scalar(ScalarValue::Int64(Some(1))),
+        ];
+        let result = match udf.invoke_batch(&args, 4).unwrap() {
+            ColumnarValue::Array(arr) => arr,
+            ColumnarValue::Scalar(_) => panic!("Expected array result"),
+        };
+        assert_eq!(
+            result.as_any().downcast_ref::<StringViewArray>().unwrap(),
+            result_array
+        );
+    }
+
+    #[test]
+    fn test_regexp_extract_with_flag_empty_optional_group() {
+        let input = StringArray::from(vec![Some("aaaac"), Some("aaabc"), Some("xyz"), None]);
+        let result = regexp_extract_with_flag(&input, "(a+)(b)?(c)", 2).unwrap();
+        let result_array = result.as_struct();
+        let value = result_array
+            .column_by_name("value")
+            .unwrap()
+            .as_string::<i32>();
+        let matched = result_array.column_by_name("matched").unwrap().as_boolean();
+
+        // The pattern matches but the optional group does not participate
+        assert_eq!(value.value(0), "");
+        assert!(matched.value(0));
+        assert_eq!(value.value(1), "b");
+        assert!(matched.value(1));
+        assert_eq!(value.value(2), "");
+        assert!(!matched.value(2));
+        assert!(result_array.is_null(3));
+    }
+
+    #[test]
+    fn test_regexp_matches_first_match() {
+        // SELECT regexp_matches('foobarbequebaz', '(bar)(beque)') returns {bar,beque}
+        let input = StringArray::from(vec![Some("foobarbequebaz"), Some("nothing"), None]);
+        let result = regexp_matches(&input, "(bar)(beque)", "").unwrap();
+        let result_array = result.as_list::<i32>();
+
+        assert_eq!(
+            result_array.value(0).as_string::<i32>(),
+            &StringArray::from(vec!["bar", "beque"])
+        );
+        assert!(result_array.is_null(1));
+        assert!(result_array.is_null(2));
+    }
+
+    #[test]
+    fn test_regexp_matches_global() {
+        // SELECT regexp_matches('foobarbequebazilbarfbonk', '(b[^b]+)(b[^b]+)', 'g')
+        // returns {bar,beque} and {bazil,barf}
+        let input = StringArray::from(vec![Some("foobarbequebazilbarfbonk"), Some("xyz"), None]);
+        let result = regexp_matches(&input, "(b[^b]+)(b[^b]+)", "g").unwrap();
+        let result_array = result.as_list::<i32>();
+
+        let matches = result_array.value(0);
+        let matches = matches.as_list::<i32>();
+        assert_eq!(matches.len(), 2);
+        assert_eq!(
+            matches.value(0).as_string::<i32>(),
+            &StringArray::from(vec!["bar", "beque"])
+        );
+        assert_eq!(
+            matches.value(1).as_string::<i32>(),
+            &StringArray::from(vec!["bazil", "barf"])
+        );
+        assert!(result_array.value(1).is_empty());
+        assert!(result_array.is_null(2));
+    }
+
+    #[test]
+    fn test_regexp_matches_without_groups() {
+        // SELECT regexp_matches('foobarbequebaz', 'barbeque') returns {barbeque}
+        let input = StringArray::from(vec![Some("foobarbequebaz")]);
+        let result = regexp_matches(&input, "barbeque", "").unwrap();
+        assert_eq!(
+            result.as_list::<i32>().value(0).as_string::<i32>(),
+            &StringArray::from(vec!["barbeque"])
+        );
+    }
+
+    #[test]
+    fn test_regexp_matches_flags() {
+        // SELECT regexp_matches('aBc aXc', 'a(b)?(x)?c', 'gi') returns {B,NULL} and {NULL,X}
+        let input = StringArray::from(vec![Some("aBc aXc")]);
+        let result = regexp_matches(&input, "a(b)?(x)?c", "gi").unwrap();
+        let matches = result.as_list::<i32>().value(0);
+        let matches = matches.as_list::<i32>();
+        assert_eq!(
+            matches.value(0).as_string::<i32>(),
+            &StringArray::from(vec![Some("B"), None])
+        );
+        assert_eq!(
+            matches.value(1).as_string::<i32>(),
+            &StringArray::from(vec![None, Some("X")])
+        );
+
+        let err = regexp_matches(&input, "a", "gz").unwrap_err();
+        assert!(err.to_string().contains("invalid regex flag 'z'"));
+    }
+
+    #[test]
+    fn test_regexp_extract_numeric() {
+        let input = Float64Array::from(vec![Some(123.45), Some(-0.5), None]);
+        let result = regexp_extract_numeric(&input, r"(\d+)", 1).unwrap();
+        assert_eq!(
+            result.as_string::<i32>(),
+            &StringArray::from(vec![Some("123"), Some("0"), None])
+        );
+
+        let input = Int64Array::from(vec![Some(20240115), None]);
+        let result = regexp_extract_numeric(&input, r"^(\d{4})(\d{2})", 2).unwrap();
+        assert_eq!(
+            result.as_string::<i32>(),
+            &StringArray::from(vec![Some("01"), None])
+        );
+
+        let err = regexp_extract_numeric(&StringArray::from(vec!["1"]), r"\d", 0).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("regexp_extract_numeric expects a numeric array"));
+    }
+
+    #[test]
+    fn test_regexp_extract_dyn() {
+        let input: ArrayRef = Arc::new(StringArray::from(vec![Some("hello123"), None]));
+        let result = regexp_extract_dyn(input.as_ref(), r"([a-z]+)(\d+)", 1).unwrap();
+        assert_eq!(
+            result.as_string::<i32>(),
+            &StringArray::from(vec![Some("hello"), None])
+        );
+
+        let input: ArrayRef = Arc::new(LargeStringArray::from(vec![Some("world456"), Some("x")]));
+        let result = regexp_extract_dyn(input.as_ref(), r"([a-z]+)(\d+)", 2).unwrap();
+        assert_eq!(
+            result.as_string::<i64>(),
+            &LargeStringArray::from(vec!["456", ""])
+        );
+    }
+
+    #[test]
+    fn test_regexp_extract_dyn_unsupported_type() {
+        let input: ArrayRef = Arc::new(BooleanArray::from(vec![true]));
+        let err = regexp_extract_dyn(input.as_ref(), r"\w+", 0).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("regexp_extract expects StringArray, LargeStringArray"));
+        assert!(err.to_string().contains("got Boolean"));
+    }
+
+    #[test]
+    fn test_regexp_extract_all_overlapping() {
+        let input = StringArray::from(vec![Some("AAAA"), Some("ATAA"), None]);
+
+        let result = regexp_extract_all_with(&input, "AA", 0, false).unwrap();
+        let result_array = result.as_list::<i32>();
+        assert_eq!(result_array.value_length(0), 2);
+        assert_eq!(result_array.value_length(1), 1);
+        assert!(result_array.is_null(2));
+
+        let result = regexp_extract_all_with(&input, "AA", 0, true).unwrap();
+        let result_array = result.as_list::<i32>();
+        assert_eq!(result_array.value_length(0), 3);
+        assert_eq!(result_array.value_length(1), 1);
+        assert!(result_array.is_null(2));
+
+        // Overlapping matches of a multi-byte pattern start on character boundaries
+        let input = StringArray::from(vec!["ééé"]);
+        let result = regexp_extract_all_with(&input, "(é)é", 1, true).unwrap();
+        assert_eq!(
+            result.as_list::<i32>().value(0).as_string::<i32>(),
+            &StringArray::from(vec!["é", "é"])
+        );
+    }
+
+    #[test]
+    fn test_regexp_extract_all_groups_overlapping() {
+        let input = StringArray::from(vec!["abcd"]);
+        let result = regexp_extract_all_groups_with(&input, "(\\w)(\\w)", true).unwrap();
+        let matches = result.as_list::<i32>().value(0);
+        let firsts = matches.as_struct().column(0).as_string::<i32>();
+        assert_eq!(firsts, &StringArray::from(vec!["a", "b", "c"]));
+    }
+
+    #[test]
+    fn test_regexp_extract_from_ignores_match_before_start() {
+        let input = StringArray::from(vec![Some("abc123def"), Some("123abc"), None]);
+        let result = regexp_extract_from(&input, r"(\d+)", 1, 3).unwrap();
+        assert_eq!(
+            result.as_string::<i32>(),
+            &StringArray::from(vec![Some("123"), Some(""), None])
+        );
+
+        // Offsets count characters, `é` takes two bytes
+        let input = StringArray::from(vec!["é1é2"]);
+        let result = regexp_extract_from(&input, r"\d", 0, 2).unwrap();
+        assert_eq!(result.as_string::<i32>().value(0), "2");
+
+        // `^` anchors at the offset, past the end nothing matches
+        let result = regexp_extract_from(&input, r"^é", 0, 2).unwrap();
+        assert_eq!(result.as_string::<i32>().value(0), "é");
+        let result = regexp_extract_from(&input, r"\d", 0, 10).unwrap();
+        assert_eq!(result.as_string::<i32>().value(0), "");
+    }
+
+    #[test]
+    fn test_regexp_extract_all_large_many_matches() {
+        let rows = 4;
+        let matches_per_row = 10_000;
+        let input = StringArray::from_iter_values((0..rows).map(|_| "x1".repeat(matches_per_row)));
+        let result = regexp_extract_all_large(&input, r"x(\d)", 1).unwrap();
+        assert_eq!(
+            result.data_type(),
+            &DataType::LargeList(Arc::new(Field::new("item", DataType::LargeUtf8, true)))
+        );
+
+        let result_array = result.as_list::<i64>();
+        let offsets: Vec<i64> = result_array.offsets().iter().copied().collect();
+        let expected: Vec<i64> = (0..=rows)
+            .map(|row| (row * matches_per_row) as i64)
+            .collect();
+        assert_eq!(offsets, expected);
+        let values = result_array.values().as_string::<i64>();
+        assert!(values.iter().all(|value| value == Some("1")));
+    }
+
+    #[test]
+    fn test_create_regexp_extract_binary_input_hint() {
+        let args = [
+            ColumnarValue::Array(Arc::new(BinaryArray::from(vec![b"abc1".as_ref()]))),
+            ColumnarValue::Scalar(ScalarValue::Utf8(Some(r"(\d)".to_string()))),
+            ColumnarValue::Scalar(ScalarValue::Int64(Some(1))),
+        ];
+        let err = create_regexp_extract().invoke_batch(&args, 1).unwrap_err();
+        assert!(err.to_string().contains("got Binary"), "{err}");
+        assert!(
+            err.to_string().contains("Use regexp_extract_binary"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_regexp_replace_with_uppercases_groups() {
+        let input = StringArray::from(vec![Some("key=value, other=thing"), Some("none"), None]);
+        let result = regexp_replace_with(&input, r"(\w+)=(\w+)", |caps| {
+            format!("{}={}", &caps[1], caps[2].to_uppercase())
+        })
+        .unwrap();
+        assert_eq!(
+            result.as_string::<i32>(),
+            &StringArray::from(vec![Some("key=VALUE, other=THING"), Some("none"), None])
+        );
+    }
+
+    #[test]
+    fn test_regexp_mask_credit_card() {
+        let input = StringArray::from(vec![
+            Some("card 4111-1111-1111-1111 on file"),
+            Some("no card"),
+            None,
+        ]);
+        let result = regexp_mask(&input, r"\d{4}-\d{4}-\d{4}-\d{4}", '#').unwrap();
+        let result_array = result.as_string::<i32>();
+        assert_eq!(result_array.value(0), "card ################### on file");
+        assert_eq!(result_array.value(0).len(), input.value(0).len());
+        assert_eq!(result_array.value(1), "no card");
+        assert!(result_array.is_null(2));
+
+        // The length is preserved in characters, also for a multi-byte mask or match
+        let input = StringArray::from(vec!["nééd"]);
+        let result = regexp_mask(&input, "é+", '•').unwrap();
+        assert_eq!(result.as_string::<i32>().value(0), "n••d");
+    }
+
+    #[test]
+    fn test_regexp_extract_dictionary_output() {
+        let udf = ScalarUDF::new_from_impl(RegexpExtract::new().with_dictionary_output(true));
+        let dictionary_type =
+            DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8));
+        let arg_types = [DataType::Utf8, DataType::Utf8, DataType::Int64];
+        assert_eq!(udf.return_type(&arg_types).unwrap(), dictionary_type);
+
+        let input = StringArray::from(vec![Some("+44 20"), Some("+1 212"), None, Some("+44 161")]);
+        let args = [
+            ColumnarValue::Array(Arc::new(input)),
+            ColumnarValue::Scalar(ScalarValue::Utf8(Some(r"\+(\d+)".to_string()))),
+            ColumnarValue::Scalar(ScalarValue::Int64(Some(1))),
+        ];
+        let result = match udf.invoke_batch(&args, 4).unwrap() {
+            ColumnarValue::Array(arr) => arr,
+            ColumnarValue::Scalar(_) => panic!("Expected array result"),
+        };
+        assert_eq!(result.data_type(), &dictionary_type);
+
+        let result_array = result.as_dictionary::<Int32Type>();
+        assert_eq!(result_array.values().len(), 2);
+        let decoded = cast(&result, &DataType::Utf8).unwrap();
+        assert_eq!(
+            decoded.as_string::<i32>(),
+            &StringArray::from(vec![Some("44"), Some("1"), None, Some("44")])
+        );
+    }
+
+    #[test]
+    fn test_regexp_extract_dictionary_output_nulls_and_empty_strings() {
+        let udf = ScalarUDF::new_from_impl(RegexpExtract::new().with_dictionary_output(true));
+        let input = StringArray::from(vec![Some("id=42"), None, Some("id="), Some("id=42")]);
+        let args = [
+            ColumnarValue::Array(Arc::new(input)),
+            ColumnarValue::Scalar(ScalarValue::Utf8(Some(r"id=(\d*)".to_string()))),
+            ColumnarValue::Scalar(ScalarValue::Int64(Some(1))),
+        ];
+        let result = match udf.invoke_batch(&args, 4).unwrap() {
+            ColumnarValue::Array(arr) => arr,
+            ColumnarValue::Scalar(_) => panic!("Expected array result"),
+        };
+
+        // The null row has a null key, the empty capture a key to an empty string value
+        let result_array = result.as_dictionary::<Int32Type>();
+        assert_eq!(
+            result_array.keys(),
+            &Int32Array::from(vec![Some(0), None, Some(1), Some(0)])
+        );
+        let values = result_array.values().as_string::<i32>();
+        assert_eq!(values, &StringArray::from(vec!["42", ""]));
+        assert_eq!(values.null_count(), 0);
+
+        // Dictionary input keeps its keys, so its null keys stay null
+        let input: DictionaryArray<Int32Type> =
+            vec![Some("id=42"), None, Some("id="), Some("id=42")]
+                .into_iter()
+                .collect();
+        let result = regexp_extract_dictionary(&input, r"id=(\d*)", 1).unwrap();
+        let result_array = result.as_dictionary::<Int32Type>();
+        assert_eq!(result_array.keys(), input.keys());
+        assert_eq!(
+            result_array.values().as_string::<i32>(),
+            &StringArray::from(vec!["42", ""])
+        );
+    }
+
+    #[test]
+    fn test_missing_arguments_return_error() {
+        let input = ColumnarValue::Array(Arc::new(StringArray::from(vec!["abc-123"])));
+        let pattern = ColumnarValue::Scalar(ScalarValue::Utf8(Some(r"(\d+)".to_string())));
+
+        let err = create_regexp_extract()
+            .invoke_batch(std::slice::from_ref(&input), 1)
+            .unwrap_err();
+        assert_eq!(
+            RegexpError::find(&err),
+            Some(&RegexpError::ArityMismatch {
+                function: "regexp_extract".to_string(),
+                min: 2,
+                max: 4,
+                actual: 1,
+            })
+        );
+        assert!(err
+            .to_string()
+            .contains("regexp_extract expects 2 to 4 arguments, got 1"));
+
+        let err = create_regexp_extract_default()
+            .invoke_batch(&[input.clone(), pattern.clone()], 1)
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("regexp_extract_default expects 4 arguments, got 2"));
+
+        // Missing position and occurrence are reported as such, not as invalid values
+        let position = ColumnarValue::Scalar(ScalarValue::Int64(Some(1)));
+        let err = create_regexp_substr()
+            .invoke_batch(&[input, pattern, position], 1)
+            .unwrap_err();
+        assert_eq!(
+            RegexpError::find(&err),
+            Some(&RegexpError::ArityMismatch {
+                function: "regexp_substr".to_string(),
+                min: 4,
+                max: 4,
+                actual: 3,
+            })
+        );
+
+        let err = create_regexp_extract_binary()
+            .invoke_batch(&[], 1)
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("regexp_extract_binary expects 3 arguments, got 0"));
+    }
+
+    #[test]
+    fn test_distinct_match_count() {
+        let input = StringArray::from(vec![
+            Some("id=7 id=3 id=7 id=7"),
+            Some("no ids"),
+            None,
+            Some("id=1"),
+        ]);
+        let matches = regexp_extract_all(&input, r"id=(\d+)", 1).unwrap();
+        let result = distinct_match_count(matches.as_list::<i32>()).unwrap();
+
+        assert_eq!(
+            result.as_primitive::<UInt32Type>(),
+            &UInt32Array::from(vec![Some(2), Some(0), None, Some(1)])
+        );
+    }
+
+    #[test]
+    fn test_distinct_match_count_rejects_non_string_lists() {
+        let list = ListArray::from_iter_primitive::<Int32Type, _, _>(vec![Some(vec![Some(1)])]);
+        let err = distinct_match_count(&list).unwrap_err();
+        assert!(matches!(
+            RegexpError::find(&err),
+            Some(RegexpError::UnsupportedInputType { actual, .. }) if actual == list.data_type()
+        ));
+    }
+
+    #[test]
+    fn test_regexp_extract_rest() {
+        let input = StringArray::from(vec![Some("key=value rest"), Some("no pair"), None]);
+        let result = regexp_extract_rest(&input, r"\w+=\w+").unwrap();
+        let result_array = result.as_struct();
+
+        let matched = result_array
+            .column_by_name("matched")
+            .unwrap()
+            .as_string::<i32>();
+        let rest = result_array
+            .column_by_name("rest")
+            .unwrap()
+            .as_string::<i32>();
+        assert_eq!((matched.value(0), rest.value(0)), ("key=value", " rest"));
+        assert_eq!((matched.value(1), rest.value(1)), ("", "no pair"));
+        assert!(result_array.is_null(2));
+    }
+
+    #[test]
+    fn test_regexp_extract_fixed_size_binary() {
+        let codes: [Option<&[u8]>; 4] = [
+            Some(b"DE-4711-A   "),
+            Some(b"FR-0001     "),
+            None,
+            Some(b"invalid code"),
+        ];
+        let input =
+            FixedSizeBinaryArray::try_from_sparse_iter_with_size(codes.into_iter(), 12).unwrap();
+
+        let result = regexp_extract_fixed_size_binary(&input, r"^[A-Z]{2}-(\d{4})", 1).unwrap();
+        assert_eq!(
+            result.as_binary::<i32>(),
+            &BinaryArray::from(vec![
+                Some(&b"4711"[..]),
+                Some(&b"0001"[..]),
+                None,
+                Some(&b""[..])
+            ])
+        );
+
+        // The space padding is part of the value, a pattern anchored at the end must match it
+        let result = regexp_extract_fixed_size_binary(&input, r"-(\w+)$", 1).unwrap();
+        assert_eq!(result.as_binary::<i32>().value(0), b"");
+        let result = regexp_extract_fixed_size_binary(&input, r"-(\w+) *$", 1).unwrap();
+        assert_eq!(result.as_binary::<i32>().value(0), b"A");
+        assert_eq!(result.as_binary::<i32>().value(1), b"0001");
+    }
+
+    #[test]
+    fn test_regexp_extract_signature() {
+        let signature = regexp_extract_signature();
+        assert_eq!(signature.volatility, Volatility::Immutable);
+        assert_eq!(&signature, create_regexp_extract().signature());
+
+        let TypeSignature::OneOf(type_signatures) = &signature.type_signature else {
+            panic!("Expected one of several exact signatures");
+        };
+        for arg_types in [
+            vec![DataType::Utf8, DataType::Utf8],
+            vec![DataType::Utf8, DataType::Utf8, DataType::Int64],
+            vec![DataType::LargeUtf8, DataType::Utf8View, DataType::UInt8],
+            vec![
+                DataType::Utf8,
+                DataType::Utf8,
+                DataType::UInt32,
+                DataType::Utf8,
+            ],
+        ] {
+            assert!(type_signatures.contains(&TypeSignature::Exact(arg_types)));
+        }
+    }
+
+    #[test]
+    fn test_compiled_extractor_reuse() {
+        let extractor = CompiledExtractor::new(r"(\w+)@(\w+)\.com", "i").unwrap();
+
+        let first = StringArray::from(vec![Some("alice@example.com"), None]);
+        let second = StringArray::from(vec![Some("no address"), Some("BOB@CORP.COM")]);
+        let third = StringArray::from(Vec::<&str>::new());
+
+        let result = extractor.extract(&first, 2).unwrap();
+        assert_eq!(
+            result.as_string::<i32>(),
+            &StringArray::from(vec![Some("example"), None])
+        );
+        let result = extractor.extract(&second, 1).unwrap();
+        assert_eq!(
+            result.as_string::<i32>(),
+            &StringArray::from(vec![Some(""), Some("BOB")])
+        );
+        assert_eq!(extractor.extract(&third, 1).unwrap().len(), 0);
+        // The first array extracts the same way after the others
+        assert_eq!(
+            extractor
+                .extract(&first, 1)
+                .unwrap()
+                .as_string::<i32>()
+                .value(0),
+            "alice"
+        );
+
+        let err = CompiledExtractor::new(r"(\w+", "").unwrap_err();
+        assert!(err.to_string().contains("invalid pattern"));
+        let err = CompiledExtractor::new(r"(\w+)", "q").unwrap_err();
+        assert!(err.to_string().contains("invalid regex flag 'q'"));
+    }
+
+    #[test]
+    fn test_regexp_extract_pair() {
+        let input = StringArray::from(vec![
+            Some("user=alice id=42"),
+            Some("user=bob"),
+            Some("nothing here"),
+            None,
+        ]);
+        let pattern = r"user=(\w+)(?: id=(\d+))?";
+
+        let result = regexp_extract_pair(&input, pattern, 1).unwrap();
+        let result_array = result.as_struct();
+        let full = result_array
+            .column_by_name("full")
+            .unwrap()
+            .as_string::<i32>();
+        let group = result_array
+            .column_by_name("group")
+            .unwrap()
+            .as_string::<i32>();
+        assert_eq!(
+            (full.value(0), group.value(0)),
+            ("user=alice id=42", "alice")
+        );
+        assert_eq!((full.value(1), group.value(1)), ("user=bob", "bob"));
+        assert_eq!((full.value(2), group.value(2)), ("", ""));
+        assert!(result_array.is_null(3));
+
+        // A group that does not participate in the match is empty, the full match is not
+        let result = regexp_extract_pair(&input, pattern, 2).unwrap();
+        let result_array = result.as_struct();
+        let full = result_array
+            .column_by_name("full")
+            .unwrap()
+            .as_string::<i32>();
+        let group = result_array
+            .column_by_name("group")
+            .unwrap()
+            .as_string::<i32>();
+        assert_eq!((full.value(0), group.value(0)), ("user=alice id=42", "42"));
+        assert_eq!((full.value(1), group.value(1)), ("user=bob", ""));
+    }
+
+    #[test]
+    fn test_regexp_extract_longest() {
+        let input = StringArray::from(vec![Some("foobar"), Some("xfoo"), Some("bar"), None]);
+
+        // Leftmost-first takes the first alternative that matches
+        let result = regexp_extract(&input, "(foo|foobar)", 1).unwrap();
+        assert_eq!(
+            result.as_string::<i32>(),
+            &StringArray::from(vec![Some("foo"), Some("foo"), Some(""), None])
+        );
+
+        let result = regexp_extract_longest(&input, "(foo|foobar)", 1).unwrap();
+        assert_eq!(
+            result.as_string::<i32>(),
+            &StringArray::from(vec![Some("foobar"), Some("foo"), Some(""), None])
+        );
+
+        // The groups are those of the longest match
+        let input = StringArray::from(vec!["abcd"]);
+        let result = regexp_extract_longest(&input, "(a|ab)(c|bcd)?", 2).unwrap();
+        assert_eq!(result.as_string::<i32>().value(0), "bcd");
+        let result = regexp_extract_longest(&input, "(a|ab)(c|bcd)?", 0).unwrap();
+        assert_eq!(result.as_string::<i32>().value(0), "abcd");
+
+        // `$` is evaluated against the whole string, not where the longest match ends, so
+        // the first alternative does not match "x" followed by "z"
+        let input = StringArray::from(vec!["xz", "x"]);
+        let pattern = r"(?:(x)$|(x)y?)";
+        let result = regexp_extract_longest(&input, pattern, 1).unwrap();
+        assert_eq!(result.as_string::<i32>(), &StringArray::from(vec!["", "x"]));
+        let result = regexp_extract_longest(&input, pattern, 2).unwrap();
+        assert_eq!(result.as_string::<i32>(), &StringArray::from(vec!["x", ""]));
+    }
+
+    #[test]
+    fn test_regexp_extract_longest_unsupported_pattern() {
+        let input = StringArray::from(vec!["foo bar"]);
+        let err = regexp_extract_longest(&input, r"\bbar", 0).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("regexp_extract_longest: invalid pattern"));
+
+        // ASCII word boundaries are supported
+        let result = regexp_extract_longest(&input, r"(?-u:\b)bar", 0).unwrap();
+        assert_eq!(result.as_string::<i32>().value(0), "bar");
+    }
+
+    #[test]
+    fn test_regexp_error_variants() {
+        let input = StringArray::from(vec!["abc123"]);
+
+        let err = regexp_extract_strict(&input, r"([a-z]+)(\d+)", 3).unwrap_err();
+        assert_eq!(
+            RegexpError::find(&err),
+            Some(&RegexpError::InvalidGroupIndex {
+                group_index: 3,
+                group_count: Some(2),
+                pattern: Some(r"([a-z]+)(\d+)".to_string()),
+            })
+        );
+        // The message stays readable
+        assert!(err.to_string().contains("max valid index is 2"), "{err}");
+
+        let err = regexp_extract(&input, "[a-", 0).unwrap_err();
+        let Some(RegexpError::InvalidPattern {
+            function, pattern, ..
+        }) = RegexpError::find(&err)
+        else {
+            panic!("Expected an invalid pattern error, got {err}");
+        };
+        assert_eq!(
+            (function.as_str(), pattern.as_str()),
+            ("regexp_extract", "[a-")
+        );
+
+        let err = regexp_extract_dyn(&BooleanArray::from(vec![true]), "a", 0).unwrap_err();
+        assert!(matches!(
+            RegexpError::find(&err),
+            Some(RegexpError::UnsupportedInputType {
+                actual: DataType::Boolean,
+                hint: None,
+                ..
+            })
+        ));
+
+        let config = RegexpExtractConfig {
+            no_match: NoMatchBehavior::Error,
+            ..RegexpExtractConfig::new(0)
+        };
+        let err = regexp_extract_with_config(&input, "xyz", &config).unwrap_err();
+        assert_eq!(
+            RegexpError::find(&err),
+            Some(&RegexpError::NoMatch {
+                pattern: "xyz".to_string(),
+            })
+        );
+
+        let err = regexp_substr(&input, r"\d+", 1, 0).unwrap_err();
+        assert_eq!(
+            RegexpError::find(&err),
+            Some(&RegexpError::ArgumentOutOfRange {
+                function: "regexp_substr".to_string(),
+                argument: "occurrence".to_string(),
+                value: 0,
+                min: 1,
+            })
+        );
+        assert!(err
+            .to_string()
+            .contains("regexp_substr: occurrence must be at least 1, got 0"));
+
+        // The regex engines only give up on a match past their configured limits, see
+        // tests/backtrack_limit.rs
+        let err = match_failed("regexp_extract_longest", "a+", "gave up");
+        assert!(matches!(
+            RegexpError::find(&err),
+            Some(RegexpError::MatchFailed { function, .. }) if function == "regexp_extract_longest"
+        ));
+
+        // Errors raised by DataFusion itself are not ours
+        let err = datafusion::error::DataFusionError::Execution("other".to_string());
+        assert_eq!(RegexpError::find(&err), None);
+    }
+
+    #[test]
+    fn test_regexp_extract_with_pos() {
+        let input = StringArray::from(vec![
+            Some("prix: 42€"),
+            Some("日本 id=7"),
+            Some("id="),
+            Some("no id"),
+            None,
+        ]);
+        let result = regexp_extract_with_pos(&input, r"id=(\d+)?", 1).unwrap();
+        let result_array = result.as_struct();
+
+        let value = result_array
+            .column_by_name("value")
+            .unwrap()
+            .as_string::<i32>();
+        let pos = result_array
+            .column_by_name("pos")
+            .unwrap()
+            .as_primitive::<Int32Type>();
+        assert_eq!((value.value(0), pos.value(0)), ("", -1));
+        // "日本 " is 7 bytes but 3 characters
+        assert_eq!((value.value(1), pos.value(1)), ("7", 6));
+        // The pattern matches but the group does not participate
+        assert_eq!((value.value(2), pos.value(2)), ("", -1));
+        assert_eq!((value.value(3), pos.value(3)), ("", -1));
+        assert!(result_array.is_null(4));
+
+        let result = regexp_extract_with_pos(&input, r"\d+", 0).unwrap();
+        let pos = result
+            .as_struct()
+            .column_by_name("pos")
+            .unwrap()
+            .as_primitive::<Int32Type>();
+        assert_eq!(pos.value(0), 6);
+        assert_eq!(pos.value(1), 6);
+    }
+
+    #[test]
+    fn test_udf_output_matches_declared_return_type() {
+        let schema = datafusion::common::DFSchema::empty();
+        for udf in all_udfs() {
+            let name = udf.name().to_string();
+            // Arguments after the input and the pattern, which must be valid for the UDF
+            let extra_string = match name.as_str() {
+                "regexp_extract_flags" => "i",
+                "regexp_extract_named" => "word",
+                "regexp_matches" => "g",
+                "regexp_extract_default"
+                | "regexp_replace"
+                | "regexp_replace_first"
+                | "regexp_replace_n" => "x",
+                "regexp_extract"
+                | "regexp_extract_binary"
+                | "regexp_extract_all"
+                | "regexp_extract_all_groups"
+                | "regexp_like"
+                | "regexp_count"
+                | "regexp_instr"
+                | "regexp_substr"
+                | "regexp_split" => "",
+                _ => panic!("No arguments for {name}, add them to this test"),
+            };
+            let type_signatures = match &udf.signature().type_signature {
+                TypeSignature::OneOf(type_signatures) => type_signatures.clone(),
+                type_signature => vec![type_signature.clone()],
+            };
+            let mut checked = 0;
+            for type_signature in type_signatures {
+                let TypeSignature::Exact(arg_types) = type_signature else {
+                    panic!("{name} has a signature that is not exact");
+                };
+                // Only string inputs and patterns, the other input types are tested on their
+                // own, and regexp_extract only accepts binary input to reject it
+                let input_type = match name.as_str() {
+                    "regexp_extract_binary" => DataType::Binary,
+                    _ => DataType::Utf8,
+                };
+                if arg_types[0] != input_type || arg_types[1] != DataType::Utf8 {
+                    continue;
+                }
+
+                let input: ArrayRef = if input_type == DataType::Binary {
+                    Arc::new(BinaryArray::from(vec![Some(&b"abc123"[..]), None]))
+                } else {
+                    Arc::new(StringArray::from(vec![Some("abc123"), None]))
+                };
+                let mut args = vec![
+                    ColumnarValue::Array(input),
+                    ColumnarValue::Scalar(ScalarValue::from(r"(?P<word>[a-z]+)(\d+)")),
+                ];
+                for arg_type in &arg_types[2..] {
+                    let scalar = match arg_type {
+                        DataType::Utf8 => ScalarValue::from(extra_string),
+                        _ => ScalarValue::Int64(Some(1)).cast_to(arg_type).unwrap(),
+                    };
+                    args.push(ColumnarValue::Scalar(scalar));
+                }
+
+                let exprs: Vec<Expr> = args
+                    .iter()
+                    .map(|arg| match arg {
+                        ColumnarValue::Scalar(scalar) => Expr::Literal(scalar.clone()),
+                        ColumnarValue::Array(_) => datafusion::prelude::col("input"),
+                    })
+                    .collect();
+                let return_type = udf
+                    .return_type_from_exprs(&exprs, &schema, &arg_types)
+                    .unwrap_or_else(|e| panic!("{name}{arg_types:?}: {e}"));
+                let output = udf
+                    .invoke_batch(&args, 2)
+                    .unwrap_or_else(|e| panic!("{name}{arg_types:?}: {e}"));
+                assert_eq!(output.data_type(), return_type, "{name}{arg_types:?}");
+                checked += 1;
+            }
+            assert!(checked > 0, "No signature of {name} was checked");
+        }
+    }
+
+    #[test]
+    fn test_regexp_extract_capacity_hint_from_previous_batches() {
+        let udf = RegexpExtract::new();
+        let batch = StringArray::from(vec![Some("user1@example.com"), None, Some("nomatch")]);
+        assert_eq!(
+            udf.capacity_hint.data_capacity(&batch),
+            string_data_len(&batch)
+        );
+
+        let args = [
+            ColumnarValue::Array(Arc::new(batch.clone())),
+            ColumnarValue::Scalar(ScalarValue::Utf8(Some(r"([a-z]+)\d+@".to_string()))),
+            ColumnarValue::Scalar(ScalarValue::Int64(Some(1))),
+        ];
+        for _ in 0..2 {
+            let ColumnarValue::Array(result) = udf.invoke_batch(&args, batch.len()).unwrap() else {
+                panic!("Expected array result");
+            };
+            assert_eq!(
+                result.as_string::<i32>(),
+                &StringArray::from(vec![Some("user"), None, Some("")])
+            );
+            // 4 bytes over 3 rows, rounded up to 2 bytes per row
+            assert_eq!(udf.capacity_hint.data_capacity(&batch), 6);
+        }
+    }
+
+    #[test]
+    fn test_regexp_error_for_flags_group_names_and_constant_arguments() {
+        let input = StringArray::from(vec!["abc"]);
+
+        let err = regexp_extract_flags(&input, "(a)", 1, "iq").unwrap_err();
+        assert_eq!(
+            RegexpError::find(&err),
+            Some(&RegexpError::InvalidFlag {
+                function: "regexp_extract_flags".to_string(),
+                flag: 'q',
+            })
+        );
+        let err = regexp_matches(&input, "(a)", "gq").unwrap_err();
+        assert!(matches!(
+            RegexpError::find(&err),
+            Some(RegexpError::InvalidFlag { flag: 'q', .. })
+        ));
+
+        let err = regexp_extract_named(&input, "(?P<word>a)", "number").unwrap_err();
+        assert_eq!(
+            RegexpError::find(&err),
+            Some(&RegexpError::UnknownGroupName {
+                function: "regexp_extract_named".to_string(),
+                group_name: "number".to_string(),
+                pattern: "(?P<word>a)".to_string(),
+            })
+        );
+
+        let args = [
+            ColumnarValue::Array(Arc::new(BinaryArray::from(vec![b"abc".as_ref()]))),
+            ColumnarValue::Scalar(ScalarValue::Utf8(Some("(a)".to_string()))),
+            ColumnarValue::Scalar(ScalarValue::Utf8(Some("1".to_string()))),
+        ];
+        let err = create_regexp_extract_binary()
+            .invoke_batch(&args, 1)
+            .unwrap_err();
+        assert!(matches!(
+            RegexpError::find(&err),
+            Some(RegexpError::UnsupportedInputType {
+                actual: DataType::Utf8,
+                ..
+            })
+        ));
+
+        let args = [
+            ColumnarValue::Array(Arc::new(input)),
+            ColumnarValue::Scalar(ScalarValue::Int64(Some(1))),
+        ];
+        let err = create_regexp_extract().invoke_batch(&args, 1).unwrap_err();
+        assert!(matches!(
+            RegexpError::find(&err),
+            Some(RegexpError::UnsupportedInputType {
+                actual: DataType::Int64,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_int32_offset_does_not_wrap() {
+        assert_eq!(int32_offset("regexp_extract_with_pos", 6).unwrap(), 6);
+        let err = int32_offset("regexp_extract_with_pos", i32::MAX as usize + 1).unwrap_err();
+        assert_eq!(
+            RegexpError::find(&err),
+            Some(&RegexpError::OffsetOverflow {
+                function: "regexp_extract_with_pos".to_string(),
+                offset: i32::MAX as usize + 1,
+            })
+        );
+        assert!(err
+            .to_string()
+            .contains("regexp_extract_with_pos: offset 2147483648 does not fit in an Int32"));
     }
 }
@@ -0,0 +1,32 @@
+//! The regexp options are a process-wide setting, so these tests run in their own
+//! binary to not affect the default limits the other tests rely on.
+#![cfg(feature = "fancy-regex")]
+
+use datafusion::arrow::array::{Array, StringArray};
+use flarion_task::{regexp_extract, set_regexp_options, RegexpError, RegexpOptions};
+
+#[test]
+fn test_backtrack_limit_exceeded() {
+    // The backreference needs the backtracking engine, and the nested quantifier tries
+    // every way to split the `a`s before failing.
+    let pattern = r"^(a+)+\1b";
+    let input = StringArray::from(vec![Some(format!("{}c", "a".repeat(30)))]);
+
+    set_regexp_options(RegexpOptions {
+        backtrack_limit: 10_000,
+        ..Default::default()
+    });
+    let err = regexp_extract(&input, pattern, 0).unwrap_err();
+    assert!(err.to_string().contains("backtracking"), "{err}");
+    assert!(matches!(
+        RegexpError::find(&err),
+        Some(RegexpError::MatchFailed { pattern: failed, .. }) if failed == pattern
+    ));
+
+    // Inputs matching within the limit are unaffected
+    let input = StringArray::from(vec![Some("aab")]);
+    let result = regexp_extract(&input, pattern, 0).unwrap();
+    let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+    assert_eq!(result_array.value(0), "aab");
+    set_regexp_options(RegexpOptions::default());
+}
@@ -0,0 +1,26 @@
+//! The regexp options are a process-wide setting, so these tests run in their own
+//! binary to not affect the default Unicode mode the other tests rely on.
+
+use datafusion::arrow::array::{AsArray, BinaryArray};
+use flarion_task::{regexp_extract_binary, set_regexp_options, RegexpOptions};
+
+#[test]
+fn test_regexp_extract_binary_unicode_mode() {
+    let input = BinaryArray::from(vec![&b"\xFFab"[..]]);
+
+    // In Unicode mode `.` only matches whole UTF-8 encoded characters
+    let result = regexp_extract_binary(&input, "(.)a", 1).unwrap();
+    assert_eq!(result.as_binary::<i32>().value(0), b"");
+
+    // Changing the options evicts the cached regex, so `.` now matches any byte
+    set_regexp_options(RegexpOptions {
+        unicode: false,
+        ..Default::default()
+    });
+    let result = regexp_extract_binary(&input, "(.)a", 1).unwrap();
+    assert_eq!(result.as_binary::<i32>().value(0), b"\xFF");
+
+    set_regexp_options(RegexpOptions::default());
+    let result = regexp_extract_binary(&input, "(.)a", 1).unwrap();
+    assert_eq!(result.as_binary::<i32>().value(0), b"");
+}
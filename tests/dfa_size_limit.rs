@@ -0,0 +1,22 @@
+//! The regexp options are a process-wide setting, so these tests run in their own
+//! binary to not affect the default limits the other tests rely on.
+
+use datafusion::arrow::array::{AsArray, StringArray};
+use flarion_task::{regexp_extract_longest, set_regexp_options, RegexpOptions};
+
+#[test]
+fn test_regexp_extract_longest_dfa_size_limit() {
+    let input = StringArray::from(vec!["foobar"]);
+
+    // A cache too small for even a few states fails to build the lazy DFA
+    set_regexp_options(RegexpOptions {
+        dfa_size_limit: 16,
+        ..Default::default()
+    });
+    let err = regexp_extract_longest(&input, "(foo|foobar)", 1).unwrap_err();
+    assert!(err.to_string().contains("regexp_extract_longest"), "{err}");
+
+    set_regexp_options(RegexpOptions::default());
+    let result = regexp_extract_longest(&input, "(foo|foobar)", 1).unwrap();
+    assert_eq!(result.as_string::<i32>().value(0), "foobar");
+}
@@ -0,0 +1,29 @@
+//! Spark compatibility is a process-wide setting, so these tests run in their own
+//! binary to not affect the permissive defaults the other tests rely on.
+
+use datafusion::arrow::array::{Array, StringArray};
+use flarion_task::{regexp_extract, set_spark_compat, spark_compat};
+
+#[test]
+fn test_spark_compat_group_index_out_of_range() {
+    let input = StringArray::from(vec![Some("abc123"), None]);
+
+    assert!(!spark_compat());
+    let result = regexp_extract(&input, r"([a-z]+)(\d+)", 3).unwrap();
+    let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+    assert_eq!(result_array.value(0), "");
+
+    set_spark_compat(true);
+    let err = regexp_extract(&input, r"([a-z]+)(\d+)", 3).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("Regex group count is 2, but the specified group index is 3"));
+
+    // Rows the pattern does not match are not checked, same as Spark.
+    let input = StringArray::from(vec![Some("nodigits")]);
+    let result = regexp_extract(&input, r"([a-z]+)(\d+)", 3).unwrap();
+    let result_array = result.as_any().downcast_ref::<StringArray>().unwrap();
+    assert_eq!(result_array.value(0), "");
+
+    set_spark_compat(false);
+}
@@ -0,0 +1,645 @@
+use datafusion::arrow::array::{
+    Array, AsArray, BooleanArray, DictionaryArray, Int32Array, LargeStringArray, StringArray,
+};
+use datafusion::arrow::datatypes::{DataType, Field, Int32Type, Schema};
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::datasource::MemTable;
+use datafusion::error::{DataFusionError, Result};
+use datafusion::execution::{FunctionRegistry, SessionStateBuilder};
+use datafusion::logical_expr::planner::ExprPlanner;
+use datafusion::logical_expr::{AggregateUDF, ScalarUDF, WindowUDF};
+use datafusion::prelude::SessionContext;
+use flarion_task::{
+    create_regexp_extract, create_regexp_extract_with, register_all, register_into,
+    NoMatchBehavior, RegexpError, RegexpExtractConfig, RegexpExtractUdfBuilder,
+};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+async fn run(ctx: &SessionContext, sql: &str) -> Vec<RecordBatch> {
+    ctx.sql(sql).await.unwrap().collect().await.unwrap()
+}
+
+#[tokio::test]
+async fn test_register_all_regexp_extract() {
+    let ctx = SessionContext::new();
+    register_all(&ctx);
+
+    let batches = run(
+        &ctx,
+        r"SELECT regexp_extract(s, '([a-z]+)(\d+)', 1) AS r
+          FROM (VALUES ('hello123'), ('world456'), ('nodigits')) AS t(s)",
+    )
+    .await;
+
+    let result = batches[0]
+        .column(0)
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .unwrap();
+    assert_eq!(result.value(0), "hello");
+    assert_eq!(result.value(1), "world");
+    assert_eq!(result.value(2), "");
+}
+
+#[tokio::test]
+async fn test_register_all_registers_every_udf() {
+    let ctx = SessionContext::new();
+    register_all(&ctx);
+
+    for name in [
+        "regexp_extract",
+        "regexp_extract_binary",
+        "regexp_extract_default",
+        "regexp_extract_flags",
+        "regexp_extract_named",
+        "regexp_extract_all",
+        "regexp_extract_all_groups",
+        "regexp_matches",
+        "regexp_replace",
+        "regexp_replace_first",
+        "regexp_replace_n",
+        "regexp_like",
+        "rlike",
+        "regexp",
+        "REGEXP_EXTRACT",
+        "REGEXP_EXTRACT_ALL",
+        "REGEXP_LIKE",
+        "RLIKE",
+        "REGEXP",
+        "regexp_count",
+        "regexp_instr",
+        "regexp_substr",
+        "regexp_split",
+        "split",
+    ] {
+        assert!(ctx.udf(name).is_ok(), "{name} is not registered");
+    }
+
+    let batches = run(
+        &ctx,
+        r"SELECT rlike(s, '\d') FROM (VALUES ('abc1'), ('abc')) AS t(s)",
+    )
+    .await;
+    let result = batches[0]
+        .column(0)
+        .as_any()
+        .downcast_ref::<BooleanArray>()
+        .unwrap();
+    assert!(result.value(0));
+    assert!(!result.value(1));
+}
+
+#[tokio::test]
+async fn test_regexp_extract_large_utf8_column() {
+    let ctx = SessionContext::new();
+    register_all(&ctx);
+
+    let batches = run(
+        &ctx,
+        r"SELECT regexp_extract(arrow_cast(s, 'LargeUtf8'), '([a-z]+)(\d+)', arrow_cast(2, 'UInt32'))
+          FROM (VALUES ('hello123'), (NULL)) AS t(s)",
+    )
+    .await;
+
+    let result = batches[0]
+        .column(0)
+        .as_any()
+        .downcast_ref::<LargeStringArray>()
+        .unwrap();
+    assert_eq!(result.value(0), "123");
+    assert!(result.is_null(1));
+}
+
+#[tokio::test]
+async fn test_regexp_extract_pattern_column() {
+    let ctx = SessionContext::new();
+    register_all(&ctx);
+
+    let batches = run(
+        &ctx,
+        r"SELECT regexp_extract(s, p, 1)
+          FROM (VALUES ('key=value', '(\w+)='), ('key=value', '=(\w+)'), ('key=value', NULL)) AS t(s, p)",
+    )
+    .await;
+
+    let result = batches[0]
+        .column(0)
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .unwrap();
+    assert_eq!(result.value(0), "key");
+    assert_eq!(result.value(1), "value");
+    assert!(result.is_null(2));
+}
+
+#[tokio::test]
+async fn test_regexp_extract_constant_input() {
+    let ctx = SessionContext::new();
+    register_all(&ctx);
+
+    let batches = run(
+        &ctx,
+        r"SELECT regexp_extract('abc123', '(\d+)', 1), regexp_extract(NULL, '(\d+)', 1)",
+    )
+    .await;
+
+    let result = batches[0]
+        .column(0)
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .unwrap();
+    assert_eq!(result.value(0), "123");
+    assert!(batches[0].column(1).is_null(0));
+}
+
+#[tokio::test]
+async fn test_regexp_extract_group_index_checked_at_planning() {
+    let ctx = SessionContext::new();
+    register_all(&ctx);
+
+    let err = ctx
+        .sql(
+            r"SELECT regexp_extract(s, '([a-z]+)(\d+)', 3)
+              FROM (VALUES ('hello123')) AS t(s)",
+        )
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("max valid index is 2"), "{err}");
+    assert!(matches!(
+        RegexpError::find(&err),
+        Some(RegexpError::InvalidGroupIndex { group_index: 3, .. })
+    ));
+
+    // With a pattern column the capture count is only known once the query runs.
+    let batches = run(
+        &ctx,
+        r"SELECT regexp_extract(s, p, 3)
+          FROM (VALUES ('hello123', '([a-z]+)(\d+)')) AS t(s, p)",
+    )
+    .await;
+    let result = batches[0]
+        .column(0)
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .unwrap();
+    assert_eq!(result.value(0), "");
+}
+
+#[tokio::test]
+async fn test_regexp_extract_default_group_index() {
+    let ctx = SessionContext::new();
+    register_all(&ctx);
+
+    let batches = run(
+        &ctx,
+        r"SELECT regexp_extract(s, '\d+'), regexp_extract(s, '([a-z]+)(\d+)', 2)
+          FROM (VALUES ('hello123'), ('nodigits')) AS t(s)",
+    )
+    .await;
+
+    let whole_match = batches[0]
+        .column(0)
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .unwrap();
+    assert_eq!(whole_match.value(0), "123");
+    assert_eq!(whole_match.value(1), "");
+    let group = batches[0]
+        .column(1)
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .unwrap();
+    assert_eq!(group.value(0), "123");
+}
+
+#[tokio::test]
+async fn test_regexp_extract_list_column() {
+    let ctx = SessionContext::new();
+    register_all(&ctx);
+
+    let batches = run(
+        &ctx,
+        r"SELECT regexp_extract(make_array('v1', 'v22'), '\d+') AS r",
+    )
+    .await;
+
+    let result = batches[0].column(0).as_list::<i32>();
+    let values = result.value(0);
+    let values = values.as_any().downcast_ref::<StringArray>().unwrap();
+    assert_eq!(values, &StringArray::from(vec!["1", "22"]));
+}
+
+#[tokio::test]
+async fn test_regexp_extract_mem_table() {
+    let schema = Arc::new(Schema::new(vec![Field::new("s", DataType::Utf8, true)]));
+    let batch = RecordBatch::try_new(
+        Arc::clone(&schema),
+        vec![Arc::new(StringArray::from(vec![
+            Some("hello123"),
+            None,
+            Some("nodigits"),
+        ]))],
+    )
+    .unwrap();
+    let table = MemTable::try_new(schema, vec![vec![batch]]).unwrap();
+
+    let ctx = SessionContext::new();
+    ctx.register_table("t", Arc::new(table)).unwrap();
+    ctx.register_udf(create_regexp_extract());
+
+    // The `1` literal is an Int64 that has to be coerced to the UInt32 group index
+    let batches = run(&ctx, r"SELECT regexp_extract(s, '([a-z]+)(\d+)', 1) FROM t").await;
+
+    assert_eq!(batches.len(), 1);
+    let result = batches[0].column(0).as_string::<i32>();
+    assert_eq!(
+        result,
+        &StringArray::from(vec![Some("hello"), None, Some("")])
+    );
+}
+
+#[tokio::test]
+async fn test_spark_aliases_match_canonical_names() {
+    let ctx = SessionContext::new();
+    register_all(&ctx);
+    let from = r"FROM (VALUES ('hello123'), ('world'), (NULL)) AS t(s)";
+
+    for (canonical, alias) in [
+        (
+            r"regexp_extract(s, '([a-z]+)(\d+)', 1)",
+            r#""REGEXP_EXTRACT"(s, '([a-z]+)(\d+)', 1)"#,
+        ),
+        (
+            r"regexp_extract_all(s, '[a-z]', arrow_cast(0, 'UInt32'))",
+            r#""REGEXP_EXTRACT_ALL"(s, '[a-z]', arrow_cast(0, 'UInt32'))"#,
+        ),
+        (r"regexp_like(s, '\d')", r"regexp(s, '\d')"),
+        (r"regexp_like(s, '\d')", r#""RLIKE"(s, '\d')"#),
+    ] {
+        let expected = run(&ctx, &format!("SELECT {canonical} AS r {from}")).await;
+        let actual = run(&ctx, &format!("SELECT {alias} AS r {from}")).await;
+        assert_eq!(actual, expected, "{alias}");
+    }
+}
+
+#[tokio::test]
+async fn test_regexp_extract_group_index_column() {
+    let ctx = SessionContext::new();
+    register_all(&ctx);
+
+    let batches = run(
+        &ctx,
+        r"SELECT regexp_extract(s, '([a-z]+)(\d+)', g)
+          FROM (VALUES ('hello123', 1), ('world456', 2), ('abc789', 1)) AS t(s, g)",
+    )
+    .await;
+
+    let result = batches[0].column(0).as_string::<i32>();
+    assert_eq!(result, &StringArray::from(vec!["hello", "456", "abc"]));
+}
+
+#[tokio::test]
+async fn test_regexp_extract_all_groups_unnest() {
+    let ctx = SessionContext::new();
+    register_all(&ctx);
+
+    let batches = run(
+        &ctx,
+        r"SELECT unnest(regexp_extract_all_groups(s, '(?P<key>\w+)=(\d+)')) AS m
+          FROM (VALUES ('a=1 b=2'), ('none'), ('c=3')) AS t(s)",
+    )
+    .await;
+
+    let batches = ctx
+        .read_batches(batches)
+        .unwrap()
+        .select_exprs(&["m['key']", "m['g2']"])
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+    let keys: Vec<&str> = batches
+        .iter()
+        .flat_map(|batch| batch.column(0).as_string::<i32>().iter().flatten())
+        .collect();
+    let values: Vec<&str> = batches
+        .iter()
+        .flat_map(|batch| batch.column(1).as_string::<i32>().iter().flatten())
+        .collect();
+    assert_eq!(keys, vec!["a", "b", "c"]);
+    assert_eq!(values, vec!["1", "2", "3"]);
+}
+
+#[tokio::test]
+async fn test_regexp_extract_udf_builders_side_by_side() {
+    let ctx = SessionContext::new();
+    ctx.register_udf(
+        RegexpExtractUdfBuilder::new()
+            .name("extract_or_null")
+            .no_match(NoMatchBehavior::Null)
+            .build(),
+    );
+    ctx.register_udf(
+        RegexpExtractUdfBuilder::new()
+            .name("extract_strict")
+            .strict(true)
+            .build(),
+    );
+
+    let batches = run(
+        &ctx,
+        r"SELECT extract_or_null(s, '([a-z]+)(\d+)', 2)
+          FROM (VALUES ('hello123'), ('nodigits')) AS t(s)",
+    )
+    .await;
+    let result = batches[0].column(0).as_string::<i32>();
+    assert_eq!(result, &StringArray::from(vec![Some("123"), None]));
+
+    // The group index column goes past the last group of the pattern
+    let sql = r"SELECT extract_strict(s, '([a-z]+)(\d+)', g)
+                FROM (VALUES ('hello123', 1), ('nodigits', 3)) AS t(s, g)";
+    let err = ctx.sql(sql).await.unwrap().collect().await.unwrap_err();
+    assert!(err.to_string().contains("out of range"), "{err}");
+    assert!(ctx.udf("regexp_extract").is_err());
+}
+
+#[tokio::test]
+async fn test_regexp_extract_int16_group_index() {
+    let ctx = SessionContext::new();
+    register_all(&ctx);
+
+    let batches = run(
+        &ctx,
+        r"SELECT regexp_extract(s, '([a-z]+)(\d+)', arrow_cast(2, 'Int16'))
+          FROM (VALUES ('hello123'), ('world456')) AS t(s)",
+    )
+    .await;
+
+    let result = batches[0].column(0).as_string::<i32>();
+    assert_eq!(result, &StringArray::from(vec!["123", "456"]));
+}
+
+#[tokio::test]
+async fn test_regexp_matches_global_flag() {
+    let ctx = SessionContext::new();
+    register_all(&ctx);
+
+    let batches = run(
+        &ctx,
+        r"SELECT regexp_matches(s, '(\w)=(\d)', 'g') AS all_matches,
+                 regexp_matches(s, '(\w)=(\d)') AS first_match
+          FROM (VALUES ('a=1 b=2')) AS t(s)",
+    )
+    .await;
+
+    let all_matches = batches[0].column(0).as_list::<i32>().value(0);
+    let all_matches = all_matches.as_list::<i32>();
+    assert_eq!(all_matches.len(), 2);
+    assert_eq!(
+        all_matches.value(1).as_string::<i32>(),
+        &StringArray::from(vec!["b", "2"])
+    );
+    let first_match = batches[0].column(1).as_list::<i32>().value(0);
+    assert_eq!(
+        first_match.as_string::<i32>(),
+        &StringArray::from(vec!["a", "1"])
+    );
+}
+
+/// Registry holding scalar UDFs only, standing in for an embedded engine's registry.
+#[derive(Default)]
+struct ScalarRegistry {
+    udfs: HashMap<String, Arc<ScalarUDF>>,
+}
+
+impl FunctionRegistry for ScalarRegistry {
+    fn udfs(&self) -> HashSet<String> {
+        self.udfs.keys().cloned().collect()
+    }
+
+    fn udf(&self, name: &str) -> Result<Arc<ScalarUDF>> {
+        self.udfs
+            .get(name)
+            .cloned()
+            .ok_or_else(|| DataFusionError::Plan(format!("no UDF named {name}")))
+    }
+
+    fn udaf(&self, name: &str) -> Result<Arc<AggregateUDF>> {
+        Err(DataFusionError::Plan(format!("no UDAF named {name}")))
+    }
+
+    fn udwf(&self, name: &str) -> Result<Arc<WindowUDF>> {
+        Err(DataFusionError::Plan(format!("no UDWF named {name}")))
+    }
+
+    fn register_udf(&mut self, udf: Arc<ScalarUDF>) -> Result<Option<Arc<ScalarUDF>>> {
+        for alias in udf.aliases() {
+            self.udfs.insert(alias.clone(), Arc::clone(&udf));
+        }
+        Ok(self.udfs.insert(udf.name().to_string(), udf))
+    }
+
+    fn expr_planners(&self) -> Vec<Arc<dyn ExprPlanner>> {
+        vec![]
+    }
+}
+
+#[tokio::test]
+async fn test_register_into_function_registry() {
+    let mut registry = ScalarRegistry::default();
+    register_into(&mut registry).unwrap();
+    assert!(registry.udf("regexp_extract").is_ok());
+    assert!(registry.udf("REGEXP_EXTRACT").is_ok());
+    assert!(registry.udf("regexp_matches").is_ok());
+
+    // A session built from a state the UDFs were registered into can call them
+    let mut state = SessionStateBuilder::new().with_default_features().build();
+    register_into(&mut state).unwrap();
+    let ctx = SessionContext::new_with_state(state);
+    let batches = run(
+        &ctx,
+        r"SELECT regexp_extract('hello123', '([a-z]+)(\d+)', 2)",
+    )
+    .await;
+    assert_eq!(
+        batches[0].column(0).as_string::<i32>(),
+        &StringArray::from(vec!["123"])
+    );
+}
+
+#[tokio::test]
+async fn test_create_regexp_extract_with_null_on_no_match() {
+    let ctx = SessionContext::new();
+    ctx.register_udf(create_regexp_extract_with(RegexpExtractConfig {
+        no_match: NoMatchBehavior::Null,
+        ..RegexpExtractConfig::new(2)
+    }));
+
+    let batches = run(
+        &ctx,
+        r"SELECT regexp_extract(s, '([a-z]+)(\d+)'), regexp_extract(s, '([a-z]+)(\d+)', 1)
+          FROM (VALUES ('hello123'), ('nodigits'), (NULL)) AS t(s)",
+    )
+    .await;
+
+    assert_eq!(
+        batches[0].column(0).as_string::<i32>(),
+        &StringArray::from(vec![Some("123"), None, None])
+    );
+    assert_eq!(
+        batches[0].column(1).as_string::<i32>(),
+        &StringArray::from(vec![Some("hello"), None, None])
+    );
+}
+
+#[tokio::test]
+async fn test_regexp_extract_float_column() {
+    let ctx = SessionContext::new();
+    register_all(&ctx);
+
+    let batches = run(
+        &ctx,
+        r"SELECT regexp_extract(x, '(\d+)', 1)
+          FROM (VALUES (123.45), (NULL), (7.0)) AS t(x)",
+    )
+    .await;
+
+    let result = batches[0].column(0).as_string::<i32>();
+    assert_eq!(
+        result,
+        &StringArray::from(vec![Some("123"), None, Some("7")])
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_shared_udf_in_concurrent_sessions() {
+    let udf = Arc::new(create_regexp_extract());
+    let (digits_ctx, letters_ctx) = (SessionContext::new(), SessionContext::new());
+    for ctx in [&digits_ctx, &letters_ctx] {
+        ctx.state_ref()
+            .write()
+            .register_udf(Arc::clone(&udf))
+            .unwrap();
+    }
+
+    // Each session alternates with the other on the cached pattern of the shared UDF
+    let query = |ctx: SessionContext, pattern: &'static str| {
+        tokio::spawn(async move {
+            let sql = format!(
+                "SELECT regexp_extract(s, '{pattern}', 1)
+                 FROM (VALUES ('abc123'), ('de45'), ('f6')) AS t(s)"
+            );
+            let mut results = vec![];
+            for _ in 0..50 {
+                let batches = run(&ctx, &sql).await;
+                results.push(batches[0].column(0).as_string::<i32>().clone());
+            }
+            results
+        })
+    };
+    let (digits, letters) = tokio::join!(
+        query(digits_ctx, r"([0-9]+)"),
+        query(letters_ctx, r"([a-z]+)")
+    );
+
+    for result in digits.unwrap() {
+        assert_eq!(result, StringArray::from(vec!["123", "45", "6"]));
+    }
+    for result in letters.unwrap() {
+        assert_eq!(result, StringArray::from(vec!["abc", "de", "f"]));
+    }
+}
+
+#[tokio::test]
+async fn test_regexp_extract_binary_column_hint() {
+    let ctx = SessionContext::new();
+    register_all(&ctx);
+
+    let sql = r"SELECT regexp_extract(arrow_cast(s, 'LargeBinary'), '(\d+)', 1)
+                FROM (VALUES ('abc123')) AS t(s)";
+    let err = ctx.sql(sql).await.unwrap_err();
+    assert!(
+        err.to_string().contains("Use regexp_extract_binary"),
+        "{err}"
+    );
+    assert!(
+        err.to_string().contains("CAST the column to VARCHAR"),
+        "{err}"
+    );
+}
+
+#[tokio::test]
+async fn test_regexp_extract_binary_integer_group_index() {
+    let ctx = SessionContext::new();
+    register_all(&ctx);
+
+    // The integer literal is typed Int64 and needs no cast, like for regexp_extract
+    let batches = run(
+        &ctx,
+        r"SELECT regexp_extract_binary(arrow_cast(s, 'Binary'), '([a-z]+)(\d+)', 2)
+          FROM (VALUES ('abc123'), ('none')) AS t(s)",
+    )
+    .await;
+
+    let result = batches[0].column(0).as_binary::<i32>();
+    assert_eq!(result.value(0), b"123");
+    assert_eq!(result.value(1), b"");
+}
+
+#[tokio::test]
+async fn test_regexp_extract_flags_argument() {
+    let ctx = SessionContext::new();
+    register_all(&ctx);
+
+    let batches = run(
+        &ctx,
+        r"SELECT regexp_extract(s, '([a-z]+)(\d+)', 1, 'i'), regexp_extract(s, '([a-z]+)(\d+)', 1)
+          FROM (VALUES ('HELLO123'), ('abc1')) AS t(s)",
+    )
+    .await;
+
+    let with_flags = batches[0].column(0).as_string::<i32>();
+    assert_eq!(with_flags, &StringArray::from(vec!["HELLO", "abc"]));
+    let without_flags = batches[0].column(1).as_string::<i32>();
+    assert_eq!(without_flags, &StringArray::from(vec!["", "abc"]));
+
+    let err = ctx
+        .sql("SELECT regexp_extract('abc', '(a)', 1, 'q')")
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("invalid regex flag 'q'"));
+}
+
+#[tokio::test]
+async fn test_regexp_extract_dictionary_column() {
+    let dictionary_type = DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8));
+    let schema = Arc::new(Schema::new(vec![Field::new(
+        "s",
+        dictionary_type.clone(),
+        true,
+    )]));
+    let input: DictionaryArray<Int32Type> = vec![Some("a1"), Some("b2"), None, Some("a1")]
+        .into_iter()
+        .collect();
+    let batch = RecordBatch::try_new(Arc::clone(&schema), vec![Arc::new(input)]).unwrap();
+    let table = MemTable::try_new(schema, vec![vec![batch]]).unwrap();
+
+    let ctx = SessionContext::new();
+    ctx.register_table("t", Arc::new(table)).unwrap();
+    ctx.register_udf(create_regexp_extract());
+
+    let batches = run(&ctx, r"SELECT regexp_extract(s, '(\d)', 1) FROM t").await;
+
+    let result = batches[0].column(0);
+    assert_eq!(result.data_type(), &dictionary_type);
+    let result = result.as_dictionary::<Int32Type>();
+    assert_eq!(
+        result.keys(),
+        &Int32Array::from(vec![Some(0), Some(1), None, Some(0)])
+    );
+    assert_eq!(
+        result.values().as_string::<i32>(),
+        &StringArray::from(vec!["1", "2"])
+    );
+}